@@ -0,0 +1,190 @@
+//! Measures how long it takes to open large 6D boards and compares it with the previous implementation, which
+//! collected the neighbours of each cell into a Vec and uncovered the opening in waves stored in HashSets.
+//! Run it with `cargo run --release --example flood_fill`.
+
+use mines6d::board::{Board, Boundary, CellState, Neighbourhood, Rules, Topology};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+type Cell = usize;
+
+/// Returns the result of f and how long it took.
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// The neighbours of the previous implementation, computed from the coordinates of the cell for every offset.
+struct OldNeighbors {
+    size: Vec<usize>,
+    strides: Vec<usize>,
+    boundaries: Vec<Boundary>,
+    offsets: Vec<Vec<isize>>,
+}
+
+impl OldNeighbors {
+    fn new(board: &Board) -> Self {
+        let size = board.size().to_vec();
+        let strides = (0..size.len())
+            .map(|i| size[..i].iter().product())
+            .collect();
+
+        Self {
+            strides,
+            boundaries: (0..size.len())
+                .map(|i| board.rules.topology.axis(i))
+                .collect(),
+            offsets: board.rules.neighbourhood.offsets(&size),
+            size,
+        }
+    }
+
+    fn neighbors(&self, cell: Cell) -> Vec<Cell> {
+        let coordinates: Vec<usize> = self
+            .size
+            .iter()
+            .zip(&self.strides)
+            .map(|(size, stride)| cell / stride % size)
+            .collect();
+        let mut result = Vec::with_capacity(self.offsets.len());
+
+        'offsets: for offset in &self.offsets {
+            let mut n = 0;
+
+            for (i, x) in coordinates.iter().enumerate() {
+                match self.boundaries[i].wrap(*x as isize + offset[i], self.size[i]) {
+                    Some(x) => n += x * self.strides[i],
+                    None => continue 'offsets,
+                }
+            }
+
+            // a wrapped offset can lead back to the cell itself
+            if n != cell {
+                result.push(n);
+            }
+        }
+
+        result
+    }
+
+    /// Returns the values of the covered cells of a board with the given mines, each cell counts the mines in the
+    /// cell or in its neighbourhood.
+    fn count_mines(&self, mines: &[Cell]) -> Vec<u16> {
+        let mut board = vec![0; self.size.iter().product()];
+        for mine in mines {
+            board[*mine] = 0x2000;
+        }
+
+        (0..board.len())
+            .map(|c| {
+                let count = std::iter::once(c)
+                    .chain(self.neighbors(c))
+                    .filter(|n| !Board::is_empty(board[*n]))
+                    .count();
+                board[c] | count as u16
+            })
+            .collect()
+    }
+
+    /// Uncovers a cell and all cells connected to it through cells without neighbouring mines, returns the number
+    /// of uncovered cells.
+    fn uncover_recursively(&self, board: &mut [u16], cell: Cell) -> usize {
+        let mut uncovered = 0;
+        let mut set = HashSet::new();
+        set.insert(cell);
+
+        loop {
+            let mut remove_set = HashSet::new();
+            let mut insert_set = HashSet::new();
+
+            for c in &set {
+                remove_set.insert(*c);
+
+                if !Board::is_uncovered(board[*c]) {
+                    uncovered += 1;
+                }
+                board[*c] |= 0xc000;
+
+                if Board::mines(board[*c]) == 0 {
+                    for n in self.neighbors(*c) {
+                        if Board::is_covered(board[n]) {
+                            insert_set.insert(n);
+                        }
+                    }
+                }
+            }
+
+            set.extend(insert_set);
+            for c in remove_set {
+                set.remove(&c);
+            }
+
+            if set.is_empty() {
+                break;
+            }
+        }
+
+        uncovered
+    }
+}
+
+fn main() {
+    let boards = [
+        ([10; 6], 1000, Neighbourhood::VonNeumann, Boundary::Bounded),
+        ([10; 6], 1000, Neighbourhood::Moore, Boundary::Bounded),
+        ([14; 6], 5000, Neighbourhood::VonNeumann, Boundary::Periodic),
+        ([8; 6], 500, Neighbourhood::Moore, Boundary::Mirrored),
+    ];
+
+    for (size, mines, neighbourhood, boundary) in boards {
+        let rules = Rules {
+            neighbourhood,
+            topology: Topology::uniform(boundary),
+            ..Rules::default()
+        };
+        let mut board = Board::new(&size, mines, 0, rules.clone(), Some(0));
+        let cells = board.cell_count();
+        let start = board.index(&[size[0] / 2]).unwrap();
+
+        // the first uncovered cell places the mines, counts them and finds the openings for the 3BV, the opening is
+        // then uncovered at once
+        let (_, first) = time(|| board.uncover_cell(start));
+        let (bbbv, _) = board.bbbv();
+
+        // both implementations uncover the same opening of a board whose mines have been placed
+        let mut covered = Board::new(&size, mines, 0, rules, Some(0));
+        covered.place_mines_at(&board.mine_cells());
+        let old = OldNeighbors::new(&board);
+
+        let (neighbors, iterate) = time(|| {
+            (0..cells)
+                .map(|c| covered.neighbors(c).count())
+                .sum::<usize>()
+        });
+        let (old_neighbors, old_iterate) =
+            time(|| (0..cells).map(|c| old.neighbors(c).len()).sum::<usize>());
+        assert_eq!(neighbors, old_neighbors);
+
+        let (_, uncover) = time(|| covered.uncover_cell(start));
+        let uncovered = covered
+            .observe()
+            .cells()
+            .filter(|(_, state)| matches!(state, CellState::Uncovered(_)))
+            .count();
+        let mut old_board = old.count_mines(&board.mine_cells());
+        let (old_uncovered, old_uncover) = time(|| old.uncover_recursively(&mut old_board, start));
+        assert_eq!(uncovered, old_uncovered);
+
+        println!(
+            "{:?}, {} mines, {}: {} cells, {} uncovered, 3BV {}, first uncover in {:.3?}",
+            size, mines, board.rules, cells, uncovered, bbbv, first
+        );
+        println!(
+            "    {} neighbours in {:.3?} (previously {:.3?}), opening uncovered in {:.3?} (previously {:.3?})",
+            neighbors, iterate, old_iterate, uncover, old_uncover
+        );
+    }
+}
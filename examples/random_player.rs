@@ -0,0 +1,30 @@
+//! Plays beginner boards by uncovering random covered cells and prints the win rate.
+
+use mines6d::{
+    board::{Action, CellState, Observation, Rules},
+    player::{play_games, Player},
+};
+use rand::{seq::IteratorRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+struct RandomPlayer {
+    rng: ChaCha8Rng,
+}
+
+impl Player for RandomPlayer {
+    fn play(&mut self, observation: &Observation) -> Option<(Action, usize)> {
+        observation
+            .cells()
+            .filter(|(_, state)| *state == CellState::Covered)
+            .choose(&mut self.rng)
+            .map(|(cell, _)| (Action::Uncover, cell))
+    }
+}
+
+fn main() {
+    let mut player = RandomPlayer {
+        rng: ChaCha8Rng::seed_from_u64(0),
+    };
+    let report = play_games(&mut player, &[9, 9], 10, 0, &Rules::default(), 1000, 0);
+    println!("{}", report);
+}
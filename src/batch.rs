@@ -0,0 +1,198 @@
+use crate::board::{Board, CellState, Uncovered};
+use serde::Serialize;
+use std::io::{BufRead, Write};
+
+#[cfg(test)]
+#[path = "batch_tests.rs"]
+mod batch_tests;
+
+/// The reply to a command, written as a single line of JSON.
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// "running", "won" or "lost"
+    pub status: String,
+    /// True if a mine has been uncovered in practice mode, the move has then been undone
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub undone: bool,
+    pub mines: u32,
+    pub flagged: u32,
+    pub marked: u32,
+    pub cheats_remaining: u32,
+    /// Size of the board as (x₁, x₂, …, xₙ), only included by the state command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<Vec<usize>>,
+    /// The cells in the order of `Board::board` (x₁ varies fastest), only included by the state command:
+    /// "#" covered, "F" flagged, "?" marked, "*" the mine that lost the game, or the number of neighbouring mines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cells: Option<Vec<String>>,
+}
+
+/// Plays a game without the user interface, driven by one command per line.
+pub struct Batch {
+    pub board: Board,
+    /// "won" or "lost" once the game has ended
+    result: Option<&'static str>,
+}
+
+impl Batch {
+    pub const fn new(board: Board) -> Self {
+        Self {
+            board,
+            result: None,
+        }
+    }
+
+    /// Reads commands until the input ends or the quit command is read, writing a response for each command.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> Result<(), String> {
+        for line in input.lines() {
+            let line = line.map_err(|err| err.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.trim() == "quit" {
+                break;
+            }
+
+            let response = self.execute(&line);
+            let string = serde_json::to_string(&response).map_err(|err| err.to_string())?;
+            writeln!(output, "{}", string)
+                .and_then(|_| output.flush())
+                .map_err(|err| err.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes a command like "uncover 1 2 0" (coordinates from x₁ on, missing ones are 0),
+    /// "flag …", "mark …", "cheat …", "chord …", "undo", "redo" or "state".
+    pub fn execute(&mut self, line: &str) -> Response {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let arguments: Vec<&str> = words.collect();
+
+        let result = match command {
+            "uncover" | "flag" | "mark" | "cheat" | "chord" => self
+                .parse_cell(&arguments)
+                .and_then(|cell| self.play(command, cell)),
+            "undo" | "redo" | "state" if !arguments.is_empty() => {
+                Err(format!("\"{}\" takes no arguments", command))
+            }
+            "undo" | "redo" => self.play(command, 0),
+            "state" => Ok(Uncovered::Safe),
+            other => Err(format!("Unknown command \"{}\"", other)),
+        };
+
+        let undone = result == Ok(Uncovered::Undone);
+        let mut response = self.response(result.err());
+        response.undone = undone;
+        if command == "state" {
+            let observation = self.board.observe();
+            response.size = Some(observation.size().to_vec());
+            response.cells = Some(observation.cells().map(|(_, s)| describe(s)).collect());
+        }
+        response
+    }
+
+    // makes a move, ending the game if it has been won or lost
+    fn play(&mut self, command: &str, cell: usize) -> Result<Uncovered, String> {
+        if self.result.is_some() {
+            return Err("The game is over".to_string());
+        }
+
+        let uncovered = match command {
+            "uncover" => self.board.uncover_cell(cell),
+            "chord" => self.board.chord_cell(cell),
+            "flag" => {
+                self.board.flag_cell(cell);
+                Uncovered::Safe
+            }
+            "mark" => {
+                self.board.mark_cell(cell);
+                Uncovered::Safe
+            }
+            "cheat" if self.board.cheats_remaining == 0 => {
+                return Err("No cheats remaining".to_string())
+            }
+            "cheat" => {
+                self.board.cheat_cell(cell);
+                Uncovered::Safe
+            }
+            "undo" if self.board.moves().is_empty() => {
+                return Err("There is no move to undo".to_string())
+            }
+            "undo" if !self.board.undo() => {
+                return Err(
+                    "Only flags and marks can be undone outside of practice mode".to_string(),
+                )
+            }
+            "redo" if !self.board.redo() => return Err("There is no move to redo".to_string()),
+            _ => Uncovered::Safe,
+        };
+
+        if uncovered == Uncovered::Lost {
+            self.result = Some("lost");
+        } else if self.board.is_won() {
+            self.result = Some("won");
+        }
+        Ok(uncovered)
+    }
+
+    // parses the coordinates of a cell given from x₁ on, returns the index of the cell
+    fn parse_cell(&self, arguments: &[&str]) -> Result<usize, String> {
+        let observation = self.board.observe();
+        let size = observation.size();
+        if arguments.len() > size.len() {
+            return Err(format!("A cell has at most {} coordinates", size.len()));
+        }
+
+        let mut cell = Vec::new();
+        for (i, (argument, size)) in arguments.iter().zip(size).enumerate() {
+            match argument.parse::<usize>() {
+                Ok(x) if x < *size => cell.push(x),
+                Ok(x) => {
+                    return Err(format!(
+                        "The coordinate x{} = {} is outside of the board, which has size {}",
+                        i + 1,
+                        x,
+                        size
+                    ))
+                }
+                Err(_) => return Err(format!("Invalid coordinate \"{}\"", argument)),
+            }
+        }
+
+        Ok(observation
+            .index(&cell)
+            .expect("the coordinates are on the board"))
+    }
+
+    fn response(&self, error: Option<String>) -> Response {
+        let observation = self.board.observe();
+        Response {
+            ok: error.is_none(),
+            error,
+            status: self.result.unwrap_or("running").to_string(),
+            undone: false,
+            mines: observation.mines_total(),
+            flagged: observation.mines_flagged(),
+            marked: observation.mines_marked(),
+            cheats_remaining: observation.cheats_remaining(),
+            size: None,
+            cells: None,
+        }
+    }
+}
+
+// describes the state of a cell
+fn describe(state: CellState) -> String {
+    match state {
+        CellState::Covered => "#".to_string(),
+        CellState::Flagged => "F".to_string(),
+        CellState::Marked => "?".to_string(),
+        CellState::Uncovered(mines) => mines.to_string(),
+        CellState::Exploded => "*".to_string(),
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod batch_tests {
+
+    use super::super::Batch;
+    use crate::board::{Board, Rules, SafeStart};
+
+    // 0 1 * 1 0 with one cheat
+    fn batch() -> Batch {
+        let rules = Rules {
+            safe_start: SafeStart::Off,
+            ..Rules::default()
+        };
+        let mut board = Board::new(&[5], 0, 1, rules, Some(0));
+        board.place_mines_at(&[2]);
+        Batch::new(board)
+    }
+
+    #[test]
+    fn test_win() {
+        let mut batch = batch();
+
+        let response = batch.execute("uncover 0");
+        assert!(response.ok);
+        assert_eq!(response.status, "running");
+        assert_eq!(response.cells, None);
+
+        let response = batch.execute("state");
+        assert_eq!(response.size, Some(vec![5]));
+        assert_eq!(response.cells.unwrap(), ["0", "1", "#", "#", "#"]);
+
+        let response = batch.execute("flag 3");
+        assert_eq!(response.flagged, 1);
+        assert_eq!(batch.execute("undo").flagged, 0);
+
+        let response = batch.execute("uncover 4");
+        assert_eq!(response.status, "won");
+        assert_eq!(response.flagged, 1);
+    }
+
+    #[test]
+    fn test_loss_and_errors() {
+        let mut batch = batch();
+
+        for (command, error) in [
+            ("uncover 5", "outside of the board"),
+            ("uncover a", "Invalid coordinate"),
+            ("uncover 0 0", "at most 1 coordinates"),
+            ("explode", "Unknown command"),
+            ("undo", "no move"),
+            ("state 1", "no arguments"),
+        ] {
+            let response = batch.execute(command);
+            assert!(!response.ok);
+            assert!(response.error.unwrap().contains(error), "{}", command);
+        }
+
+        assert_eq!(batch.execute("uncover 2").status, "lost");
+        assert!(!batch.execute("uncover 0").ok);
+        assert_eq!(batch.execute("state").cells.unwrap()[2], "*");
+    }
+
+    #[test]
+    fn test_practice() {
+        let mut batch = batch();
+        batch.board.rules.practice = true;
+
+        assert!(!batch.execute("uncover 0").undone);
+        let response = batch.execute("uncover 2");
+        assert!(response.ok);
+        assert!(response.undone);
+        assert_eq!(response.status, "running");
+        assert!(!batch.execute("uncover 4").undone);
+    }
+
+    #[test]
+    fn test_run() {
+        let mut output = Vec::new();
+        batch()
+            .run(&b"uncover 0\n\nstate\nquit\nuncover 4\n"[..], &mut output)
+            .unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["ok"], true);
+        assert_eq!(lines[0].get("undone"), None);
+        assert_eq!(lines[1]["cells"][1], "1");
+    }
+}
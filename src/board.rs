@@ -1,19 +1,214 @@
-use ndarray::prelude::*;
-use rand::Rng;
-use std::{collections::HashSet, vec::Vec};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::solver;
+use std::{collections::VecDeque, fmt, str::FromStr, vec::Vec};
+
+mod bbbv;
+mod layout;
+mod moves;
+mod neighbourhood;
+mod observation;
+mod region;
+mod topology;
+use bbbv::Bbbv;
+use layout::Layout;
+pub use layout::Neighbors;
+pub use moves::{Action, Command, Move, MoveCounts, Uncovered};
+pub use neighbourhood::Neighbourhood;
+pub use observation::{CellState, Observation};
+pub use region::{Region, SafeRegions};
+pub use topology::{Boundary, Topology};
 
 #[cfg(test)]
 #[path = "board_tests.rs"]
 mod board_tests;
 
+/// The index of a cell in `Board::board`, see `Board::index`.
+type Cell = usize;
+
+/// The largest number of cells a board may have.
+pub const MAX_CELLS: usize = 1 << 24;
+
+/// The largest number of axes a board may have.
+pub const MAX_DIMENSIONS: usize = 10;
+
+/// Defines which cells are guaranteed to be free of mines when the first cell is uncovered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SafeStart {
+    /// The mines are placed when the board is created, the first cell may contain a mine.
+    Off,
+    /// The first uncovered cell is free of mines.
+    #[default]
+    Cell,
+    /// The first uncovered cell and its neighbours are free of mines.
+    Neighbourhood,
+}
+
+impl fmt::Display for SafeStart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Cell => write!(f, "cell"),
+            Self::Neighbourhood => write!(f, "neighbourhood"),
+        }
+    }
+}
+
+impl FromStr for SafeStart {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "off" | "none" => Ok(Self::Off),
+            "cell" => Ok(Self::Cell),
+            "neighbourhood" | "neighborhood" => Ok(Self::Neighbourhood),
+            other => Err(format!("Unknown safe start \"{}\"", other)),
+        }
+    }
+}
+
+/// Defines how the mines are placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Generator {
+    /// The mines are placed randomly.
+    #[default]
+    Random,
+    /// The mines are placed randomly until the board can be solved without guessing, using at most the
+    /// given number of attempts. If no attempt succeeds, the board that could be solved the furthest is used.
+    NoGuess(u32),
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Random => write!(f, "random"),
+            Self::NoGuess(attempts) => write!(f, "no-guess:{}", attempts),
+        }
+    }
+}
+
+impl FromStr for Generator {
+    type Err = String;
+
+    /// Parses "random", "no-guess" or "no-guess:attempts".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+
+        match s.split_once(':') {
+            None if s == "random" => Ok(Self::Random),
+            None if s == "no-guess" => Ok(Self::NoGuess(100)),
+            Some(("no-guess", attempts)) => match attempts.trim().parse() {
+                Ok(attempts) if attempts > 0 => Ok(Self::NoGuess(attempts)),
+                _ => Err(format!("Invalid number of attempts \"{}\"", attempts)),
+            },
+            _ => Err(format!("Unknown generator \"{}\"", s)),
+        }
+    }
+}
+
+/// Defines when the game is won.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum WinCondition {
+    /// All mines have been flagged and no other cell has been flagged.
+    Flags,
+    /// All cells that don't contain a mine have been uncovered, the remaining mines are flagged automatically.
+    Uncover,
+    /// Either of the above.
+    #[default]
+    Any,
+}
+
+impl fmt::Display for WinCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Flags => write!(f, "flags"),
+            Self::Uncover => write!(f, "uncover"),
+            Self::Any => write!(f, "any"),
+        }
+    }
+}
+
+impl FromStr for WinCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "flags" | "flag" => Ok(Self::Flags),
+            "uncover" => Ok(Self::Uncover),
+            "any" | "either" => Ok(Self::Any),
+            other => Err(format!("Unknown win condition \"{}\"", other)),
+        }
+    }
+}
+
+/// The rules of the game.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Rules {
+    pub neighbourhood: Neighbourhood,
+    pub topology: Topology,
+    pub safe_start: SafeStart,
+    pub generator: Generator,
+    pub win_condition: WinCondition,
+    /// Allows undoing uncovered cells and cheats, flags and marks can always be undone
+    pub practice: bool,
+    pub safe_regions: SafeRegions,
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}, {}", self.neighbourhood, self.topology)?;
+
+        if let Generator::NoGuess(_) = self.generator {
+            write!(f, ", no-guess")?;
+        }
+        if self.win_condition != WinCondition::Any {
+            write!(f, ", win by {}", self.win_condition)?;
+        }
+        if self.practice {
+            write!(f, ", practice")?;
+        }
+        if !self.safe_regions.0.is_empty() {
+            write!(f, ", safe regions {}", self.safe_regions)?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Stores the state of the board and handles the game logic.
+#[derive(Clone)]
 pub struct Board {
-    pub board: Array6<u16>,
+    /// The values of the cells, x₁ varies fastest, see `Board::index`
+    pub(crate) board: Vec<u16>,
+    /// Size of the board and the neighbours of each cell according to `self.rules`
+    layout: Layout,
+    pub rules: Rules,
     pub mines_total: u32,
     pub mines_flagged: u32,
     pub mines_marked: u32,
     pub cheats_total: u32,
     pub cheats_remaining: u32,
+    /// Mines are placed when the first cell is uncovered, unless `self.rules.safe_start` is `SafeStart::Off`
+    pub mines_placed: bool,
+    /// Seed of the random number generator used to place the mines
+    pub(crate) seed: u64,
+    /// None if the mines have been placed randomly, otherwise whether the no-guess generator succeeded
+    pub solvable: Option<bool>,
+    /// The moves that have been made, the last one is undone first
+    pub(crate) moves: Vec<Move>,
+    /// The moves that have been undone, the last one is redone first
+    undone: Vec<Move>,
+    /// Number of moves that have been undone
+    pub undos: u32,
+    /// Number of moves that changed the board, excluding redone moves
+    pub move_counts: MoveCounts,
+    /// Every command that has been executed, including those that didn't change anything
+    pub commands: Vec<Command>,
+    /// The mine that has been uncovered, i.e. the game has been lost
+    pub exploded: Option<Cell>,
+    /// The total and the solved 3BV, see `Board::bbbv`
+    bbbv: Bbbv,
 }
 
 impl Board {
@@ -25,31 +220,149 @@ impl Board {
     bits 12-0  : number of mines in the cell or in the neighbouring cells
     */
 
-    pub fn new(size: (usize, usize, usize, usize, usize, usize), mines: u32, cheats: u32) -> Self {
-        let board = Array6::<u16>::zeros(size);
-
-        let (x6, x5, x4, x3, x2, x1) = size;
-        let cells_total = (x6 * x5 * x4 * x3 * x2 * x1) as u32;
+    /// Creates a new board of the given size (x₁, x₂, …, xₙ), the mines are placed immediately if
+    /// `rules.safe_start` is `SafeStart::Off` and `rules.generator` is `Generator::Random`.
+    /// The same seed (and first uncovered cell) always results in the same board, a random seed is used if it is None.
+    ///
+    /// # Panics
+    /// Panics if the board is empty, too large or has too many axes, if it has no room for a cell free of mines
+    /// or if a cell could have too many neighbours, use `Board::validate` to check this.
+    pub fn new(size: &[usize], mines: u32, cheats: u32, rules: Rules, seed: Option<u64>) -> Self {
+        if let Err(err) = Self::validate(size, mines, &rules) {
+            panic!("{}", err);
+        }
 
-        let mines = if mines > cells_total {
-            cells_total
-        } else {
-            mines
-        };
+        let layout = Layout::new(size, &rules);
 
         let mut b = Self {
-            board,
+            board: vec![0; layout.len()],
+            layout,
+            rules,
             mines_total: mines,
             mines_flagged: 0,
             mines_marked: 0,
             cheats_total: cheats,
             cheats_remaining: cheats,
+            mines_placed: false,
+            seed: seed.unwrap_or_else(|| rand::thread_rng().gen()),
+            solvable: None,
+            moves: Vec::new(),
+            undone: Vec::new(),
+            undos: 0,
+            move_counts: MoveCounts::default(),
+            commands: Vec::new(),
+            exploded: None,
+            bbbv: Bbbv::default(),
         };
-        b.place_mines(b.mines_total);
+
+        if b.rules.safe_start == SafeStart::Off && b.rules.generator == Generator::Random {
+            let mut rng = ChaCha8Rng::seed_from_u64(b.seed);
+            let safe_zone = b.rules.safe_regions.mask(size);
+            b.place_mines(b.mines_total, &safe_zone, &mut rng);
+        }
 
         b
     }
 
+    /// Returns the number of cells of a board having the given size, or an error message if the board is empty,
+    /// has more than `MAX_CELLS` cells or more than `MAX_DIMENSIONS` axes.
+    pub fn cells(size: &[usize]) -> Result<usize, String> {
+        if size.len() > MAX_DIMENSIONS {
+            return Err(format!(
+                "The board has {} axes, at most {} are supported",
+                size.len(),
+                MAX_DIMENSIONS
+            ));
+        }
+
+        let cells = size
+            .iter()
+            .try_fold(1usize, |cells, x| cells.checked_mul(*x))
+            .filter(|cells| *cells <= MAX_CELLS);
+
+        match cells {
+            Some(_) if size.is_empty() => Err("The board is empty".to_string()),
+            Some(0) => Err("The board is empty".to_string()),
+            Some(cells) => Ok(cells),
+            None => Err(format!(
+                "The board has more than {} cells, which is not supported",
+                MAX_CELLS
+            )),
+        }
+    }
+
+    /// Returns an error message if a board having the given size, number of mines and rules can't be created.
+    pub fn validate(size: &[usize], mines: u32, rules: &Rules) -> Result<(), String> {
+        let cells = Self::cells(size)?;
+
+        // at least one cell must be free of mines
+        if mines as usize >= cells {
+            return Err(format!(
+                "{} mines don't fit on a board having {} cells, at most {} are possible",
+                mines,
+                cells,
+                cells - 1
+            ));
+        }
+
+        // the first uncovered cell has to stay free of mines as well, it may lie outside of the safe regions
+        rules.safe_regions.validate(size)?;
+        let safe = rules.safe_regions.mask(size).iter().filter(|s| **s).count();
+        let start = rules.safe_start != SafeStart::Off || rules.generator != Generator::Random;
+        let room = (cells - safe).saturating_sub(start as usize);
+        if mines as usize > room {
+            return Err(format!(
+                "The safe regions cover {} cells, at most {} mines fit on the remaining cells",
+                safe, room
+            ));
+        }
+
+        rules.neighbourhood.validate(size)
+    }
+
+    /// Returns what the player may know about the board.
+    pub const fn observe(&self) -> Observation<'_> {
+        Observation::new(self)
+    }
+
+    /// Returns the size of the board as (x₁, x₂, …, xₙ).
+    pub fn size(&self) -> &[usize] {
+        &self.layout.size
+    }
+
+    /// Returns the index in `self.board` of the cell at the given coordinates (x₁, x₂, …), missing coordinates
+    /// are 0. Returns None if the cell lies outside of the board.
+    pub fn index(&self, coordinates: &[usize]) -> Option<Cell> {
+        self.layout.index(coordinates)
+    }
+
+    /// Returns the coordinates (x₁, x₂, …, xₙ) of a cell.
+    pub fn coordinates(&self, cell: Cell) -> Vec<usize> {
+        self.layout.coordinates(cell)
+    }
+
+    /// Returns the number of cells of the board.
+    pub fn cell_count(&self) -> usize {
+        self.board.len()
+    }
+
+    /// Returns the cells containing a mine, e.g. to record the board. Players should use `Board::observe` instead.
+    pub fn mine_cells(&self) -> Vec<Cell> {
+        (0..self.board.len())
+            .filter(|cell| !Self::is_empty(self.board[*cell]))
+            .collect()
+    }
+
+    /// Returns the seed of the random number generator used to place the mines.
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the moves that have been made, the last one is undone first.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
     /// Checks if a cell having value is covered.
     pub const fn is_covered(value: u16) -> bool {
         value & 0xc000 == 0x0000
@@ -80,95 +393,95 @@ impl Board {
         value & 0x1fff
     }
 
-    // Returns all neighbors of the given cell
-    pub fn neighbors(
-        &self,
-        cell: (usize, usize, usize, usize, usize, usize),
-    ) -> Vec<(usize, usize, usize, usize, usize, usize)> {
-        let mut result = Vec::new();
-        let (x6, x5, x4, x3, x2, x1) = cell;
-        let (s6, s5, s4, s3, s2, s1) = self.board.dim();
-
-        if x6 > 0 {
-            result.push((x6 - 1, x5, x4, x3, x2, x1));
-        }
-        if x6 < s6 - 1 {
-            result.push((x6 + 1, x5, x4, x3, x2, x1));
-        }
-        if x5 > 0 {
-            result.push((x6, x5 - 1, x4, x3, x2, x1));
-        }
-        if x5 < s5 - 1 {
-            result.push((x6, x5 + 1, x4, x3, x2, x1));
-        }
-        if x4 > 0 {
-            result.push((x6, x5, x4 - 1, x3, x2, x1));
-        }
-        if x4 < s4 - 1 {
-            result.push((x6, x5, x4 + 1, x3, x2, x1));
-        }
-        if x3 > 0 {
-            result.push((x6, x5, x4, x3 - 1, x2, x1));
-        }
-        if x3 < s3 - 1 {
-            result.push((x6, x5, x4, x3 + 1, x2, x1));
-        }
-        if x2 > 0 {
-            result.push((x6, x5, x4, x3, x2 - 1, x1));
-        }
-        if x2 < s2 - 1 {
-            result.push((x6, x5, x4, x3, x2 + 1, x1));
-        }
-        if x1 > 0 {
-            result.push((x6, x5, x4, x3, x2, x1 - 1));
-        }
-        if x1 < s1 - 1 {
-            result.push((x6, x5, x4, x3, x2, x1 + 1));
-        }
-
-        result
+    /// Returns the neighbours of the given cell according to `self.rules`, without allocating.
+    /// A cell that is reached by multiple offsets (e.g. through a mirrored edge) is returned multiple times.
+    pub fn neighbors(&self, cell: Cell) -> Neighbors<'_> {
+        self.layout.neighbors(cell)
     }
 
     /// Decrements `self.cheats_remaining` and reveals the contents of a covered cell if `self.cheats_remaining` > 0.
-    /// Returns true if all mines have been correctly identified.
-    pub fn cheat_cell(&mut self, cell: (usize, usize, usize, usize, usize, usize)) -> bool {
+    /// Returns true if the game has been won.
+    pub fn cheat_cell(&mut self, cell: Cell) -> bool {
+        self.commands.push(Command::Cheat(cell));
         if self.cheats_remaining == 0 {
             return false;
         }
 
-        let (x6, x5, x4, x3, x2, x1) = cell;
+        self.place_mines_around(cell);
+        let counters = self.counters();
 
-        if Self::is_empty(self.board[[x6, x5, x4, x3, x2, x1]])
-            && !Self::is_uncovered(self.board[[x6, x5, x4, x3, x2, x1]])
-        {
+        if Self::is_empty(self.board[cell]) && !Self::is_uncovered(self.board[cell]) {
             self.cheats_remaining -= 1;
-            self.uncover_recursively(cell);
-            false
-        } else if !Self::is_uncovered(self.board[[x6, x5, x4, x3, x2, x1]]) {
+            let mut changes = self.uncover_recursively(cell);
+            changes.extend(self.flag_remaining_mines());
+            self.record(Action::Cheat, cell, changes, counters);
+            self.is_won()
+        } else if !Self::is_uncovered(self.board[cell]) {
             self.cheats_remaining -= 1;
-            self.flag_cell(cell)
+            let previous = self.board[cell];
+            self.toggle_flag(cell);
+            self.record(Action::Cheat, cell, vec![(cell, previous)], counters);
+            self.is_won()
         } else {
             false
         }
     }
 
-    /// Flags a cell as containing a mine, returns true if all mines have been correctly identified.
-    pub fn flag_cell(&mut self, cell: (usize, usize, usize, usize, usize, usize)) -> bool {
-        let (x6, x5, x4, x3, x2, x1) = cell;
+    /// Flags a cell as containing a mine, returns true if the game has been won.
+    pub fn flag_cell(&mut self, cell: Cell) -> bool {
+        self.commands.push(Command::Flag(cell));
+        let counters = self.counters();
+        let previous = self.board[cell];
 
-        if !Self::is_flagged(self.board[[x6, x5, x4, x3, x2, x1]])
-            && Self::is_covered(self.board[[x6, x5, x4, x3, x2, x1]])
-        {
-            self.board[[x6, x5, x4, x3, x2, x1]] =
-                (self.board[[x6, x5, x4, x3, x2, x1]] | 0x4000) & 0x7fff;
-            self.mines_flagged += 1;
-        } else if Self::is_flagged(self.board[[x6, x5, x4, x3, x2, x1]]) {
-            self.board[[x6, x5, x4, x3, x2, x1]] &= 0x3fff;
-            self.mines_flagged -= 1;
+        self.toggle_flag(cell);
+        self.record(Action::Flag, cell, vec![(cell, previous)], counters);
+
+        self.is_won()
+    }
+
+    /// Returns true if the game has been won according to `self.rules.win_condition`.
+    pub fn is_won(&self) -> bool {
+        match self.rules.win_condition {
+            WinCondition::Flags => self.all_mines_flagged(),
+            WinCondition::Uncover => self.all_safe_cells_uncovered(),
+            WinCondition::Any => self.all_mines_flagged() || self.all_safe_cells_uncovered(),
+        }
+    }
+
+    /// Returns true if all cells that don't contain a mine have been uncovered.
+    pub fn all_safe_cells_uncovered(&self) -> bool {
+        self.mines_placed
+            && self
+                .board
+                .iter()
+                .all(|v| !Self::is_empty(*v) || Self::is_uncovered(*v))
+    }
+
+    /// Flags all mines that haven't been flagged if the game has been won by uncovering all other cells,
+    /// returns the flagged cells together with their previous values.
+    fn flag_remaining_mines(&mut self) -> Vec<(Cell, u16)> {
+        if self.rules.win_condition == WinCondition::Flags || !self.all_safe_cells_uncovered() {
+            return Vec::new();
+        }
+
+        let mut flagged = Vec::new();
+        for (cell, value) in self.board.iter_mut().enumerate() {
+            if !Self::is_empty(*value) && !Self::is_flagged(*value) {
+                if Self::is_marked(*value) {
+                    self.mines_marked -= 1;
+                }
+                flagged.push((cell, *value));
+                *value = (*value & 0x3fff) | 0x4000;
+                self.mines_flagged += 1;
+            }
         }
 
-        // are all mines flagged correctly ?
-        if self.mines_flagged == self.mines_total {
+        flagged
+    }
+
+    /// Returns true if all mines have been flagged and no other cell has been flagged.
+    pub fn all_mines_flagged(&self) -> bool {
+        if self.mines_placed && self.mines_flagged == self.mines_total {
             for cell in self.board.iter() {
                 if Self::is_flagged(*cell) && Self::is_empty(*cell) {
                     return false;
@@ -181,105 +494,371 @@ impl Board {
         }
     }
 
+    /// Flags a covered cell or removes the flag of a flagged cell.
+    fn toggle_flag(&mut self, cell: Cell) {
+        if !Self::is_flagged(self.board[cell]) && Self::is_covered(self.board[cell]) {
+            self.board[cell] = (self.board[cell] | 0x4000) & 0x7fff;
+            self.mines_flagged += 1;
+        } else if Self::is_flagged(self.board[cell]) {
+            self.board[cell] &= 0x3fff;
+            self.mines_flagged -= 1;
+        }
+    }
+
     /// Marks a cell as maybe containing a mine.
-    pub fn mark_cell(&mut self, cell: (usize, usize, usize, usize, usize, usize)) {
-        let (x6, x5, x4, x3, x2, x1) = cell;
+    pub fn mark_cell(&mut self, cell: Cell) {
+        self.commands.push(Command::Mark(cell));
+        let counters = self.counters();
+        let previous = self.board[cell];
 
-        if Self::is_covered(self.board[[x6, x5, x4, x3, x2, x1]]) {
-            self.board[[x6, x5, x4, x3, x2, x1]] =
-                (self.board[[x6, x5, x4, x3, x2, x1]] | 0x8000) & 0xbfff;
+        if Self::is_covered(self.board[cell]) {
+            self.board[cell] = (self.board[cell] | 0x8000) & 0xbfff;
             self.mines_marked += 1;
-        } else if Self::is_marked(self.board[[x6, x5, x4, x3, x2, x1]]) {
-            self.board[[x6, x5, x4, x3, x2, x1]] &= 0x3fff;
+        } else if Self::is_marked(self.board[cell]) {
+            self.board[cell] &= 0x3fff;
             self.mines_marked -= 1;
         }
+
+        self.record(Action::Mark, cell, vec![(cell, previous)], counters);
+    }
+
+    /// Marks a cell as uncovered, returns whether this uncovered a mine.
+    pub fn uncover_cell(&mut self, cell: Cell) -> Uncovered {
+        self.commands.push(Command::Uncover(cell));
+        self.place_mines_around(cell);
+
+        if !Self::is_empty(self.board[cell]) {
+            self.explode(cell)
+        } else if Self::is_covered(self.board[cell]) {
+            let counters = self.counters();
+            let mut changes = self.uncover_recursively(cell);
+            changes.extend(self.flag_remaining_mines());
+            self.record(Action::Uncover, cell, changes, counters);
+            Uncovered::Safe
+        } else {
+            Uncovered::Safe
+        }
     }
 
-    /// Marks a cell as uncovered, returns true if this results in the game being lost.
-    pub fn uncover_cell(&mut self, cell: (usize, usize, usize, usize, usize, usize)) -> bool {
-        let (x6, x5, x4, x3, x2, x1) = cell;
+    /// Uncovers all covered neighbours of an uncovered cell if the number of flagged neighbours equals its number,
+    /// returns whether this uncovered a mine, i.e. whether a flag was wrong.
+    /// Marked neighbours are not uncovered.
+    pub fn chord_cell(&mut self, cell: Cell) -> Uncovered {
+        self.commands.push(Command::Chord(cell));
+        let value = self.board[cell];
+        if !Self::is_uncovered(value) || !Self::is_empty(value) {
+            return Uncovered::Safe;
+        }
 
-        if !Self::is_empty(self.board[[x6, x5, x4, x3, x2, x1]]) {
-            true
-        } else if Self::is_covered(self.board[[x6, x5, x4, x3, x2, x1]]) {
-            self.uncover_recursively(cell);
-            false
+        // neighbours reached through multiple offsets are counted multiple times, just like the mines
+        let flagged = self
+            .neighbors(cell)
+            .filter(|n| Self::is_flagged(self.board[*n]))
+            .count();
+        if flagged != Self::mines(value) as usize {
+            return Uncovered::Safe;
+        }
+
+        let covered: Vec<_> = self
+            .neighbors(cell)
+            .filter(|n| Self::is_covered(self.board[*n]))
+            .collect();
+        if let Some(mine) = covered.iter().find(|n| !Self::is_empty(self.board[**n])) {
+            return self.explode(*mine);
+        }
+
+        let counters = self.counters();
+        let mut changes = Vec::new();
+        for n in covered {
+            // a previous neighbour might have uncovered this one already
+            if Self::is_covered(self.board[n]) {
+                changes.extend(self.uncover_recursively(n));
+            }
+        }
+        changes.extend(self.flag_remaining_mines());
+        self.record(Action::Chord, cell, changes, counters);
+
+        Uncovered::Safe
+    }
+
+    /// Stores the uncovered mine that lost the game. In practice mode the game isn't lost, the mine stays covered
+    /// instead, which counts as undoing the move.
+    fn explode(&mut self, cell: Cell) -> Uncovered {
+        if self.rules.practice {
+            self.undos += 1;
+            Uncovered::Undone
         } else {
-            false
+            self.exploded = Some(cell);
+            Uncovered::Lost
         }
     }
 
-    /// Recursively uncovers empty cells.
-    fn uncover_recursively(&mut self, cell: (usize, usize, usize, usize, usize, usize)) {
-        let mut set = HashSet::new();
-        set.insert(cell);
+    /// Returns the 3BV of the board, i.e. the minimal number of uncovered cells required to uncover all cells without
+    /// a mine, together with the 3BV that has been solved. Each opening (a connected region of cells without
+    /// neighbouring mines, including its border) counts once, every other cell without a mine counts once as well.
+    /// Both are 0 until the mines have been placed.
+    pub const fn bbbv(&self) -> (u32, u32) {
+        (self.bbbv.total, self.bbbv.solved)
+    }
 
-        // repeat until the set is empty
-        loop {
-            // using these sets is required to avoid a mutable iterator
-            let mut remove_set = HashSet::new(); // holds the elements that should be removed from set
-            let mut insert_set = HashSet::new(); // holds the elements that should be inserted into set
+    /// Returns the solved 3BV per move in percent, None if no move has been made.
+    pub fn efficiency(&self) -> Option<f64> {
+        match self.move_counts.total() {
+            0 => None,
+            moves => Some(self.bbbv().1 as f64 / moves as f64 * 100.0),
+        }
+    }
 
-            for c in &set {
-                let (x6, x5, x4, x3, x2, x1) = *c;
+    /// Returns true if the last move can be undone, uncovered cells and cheats can only be undone in practice mode.
+    pub fn can_undo(&self) -> bool {
+        match self.moves.last() {
+            Some(m) => self.rules.practice || matches!(m.action, Action::Flag | Action::Mark),
+            None => false,
+        }
+    }
 
-                // remove cell from set
-                remove_set.insert(*c);
+    /// Undoes the last move if `self.can_undo()`, returns true if a move has been undone.
+    /// The mines stay in place if they have been placed by the move.
+    pub fn undo(&mut self) -> bool {
+        self.commands.push(Command::Undo);
+        if !self.can_undo() {
+            return false;
+        }
 
-                // uncover cell
-                self.board[[x6, x5, x4, x3, x2, x1]] |= 0xc000;
+        if let Some(m) = self.moves.pop() {
+            for (cell, before, _) in &m.changes {
+                self.board[*cell] = (self.board[*cell] & 0x3fff) | before;
+            }
+            self.bbbv.apply(&m.changes, true);
+            self.set_counters(m.counters.0);
+            self.undone.push(m);
+            self.undos += 1;
+        }
 
-                // store covered neighbors if the cell has no mines as neighbors
-                if Self::mines(self.board[[x6, x5, x4, x3, x2, x1]]) == 0 {
-                    for n in self.neighbors(*c) {
-                        let (x6, x5, x4, x3, x2, x1) = n;
+        true
+    }
 
-                        if Self::is_covered(self.board[[x6, x5, x4, x3, x2, x1]]) {
-                            insert_set.insert(n);
-                        }
-                    }
+    /// Redoes the last undone move, returns true if a move has been redone.
+    pub fn redo(&mut self) -> bool {
+        self.commands.push(Command::Redo);
+        match self.undone.pop() {
+            Some(m) => {
+                for (cell, _, after) in &m.changes {
+                    self.board[*cell] = (self.board[*cell] & 0x3fff) | after;
                 }
+                self.bbbv.apply(&m.changes, false);
+                self.set_counters(m.counters.1);
+                self.moves.push(m);
+                true
             }
+            None => false,
+        }
+    }
 
-            // update set
-            set.extend(insert_set);
-            for c in remove_set {
-                set.remove(&c);
+    /// Executes a command, e.g. to restore a saved game. The cell of the command has to be on the board.
+    pub fn execute(&mut self, command: Command) {
+        match command {
+            Command::Uncover(cell) => {
+                self.uncover_cell(cell);
+            }
+            Command::Flag(cell) => {
+                self.flag_cell(cell);
             }
+            Command::Mark(cell) => self.mark_cell(cell),
+            Command::Cheat(cell) => {
+                self.cheat_cell(cell);
+            }
+            Command::Chord(cell) => {
+                self.chord_cell(cell);
+            }
+            Command::Undo => {
+                self.undo();
+            }
+            Command::Redo => {
+                self.redo();
+            }
+        }
+    }
+
+    /// Returns `self.mines_flagged`, `self.mines_marked` and `self.cheats_remaining`.
+    const fn counters(&self) -> [u32; 3] {
+        [self.mines_flagged, self.mines_marked, self.cheats_remaining]
+    }
+
+    /// Sets `self.mines_flagged`, `self.mines_marked` and `self.cheats_remaining`.
+    fn set_counters(&mut self, counters: [u32; 3]) {
+        self.mines_flagged = counters[0];
+        self.mines_marked = counters[1];
+        self.cheats_remaining = counters[2];
+    }
+
+    /// Appends a move to `self.moves` given the previous values of the cells it might have changed,
+    /// moves that didn't change anything are omitted. Clears the undone moves.
+    fn record(
+        &mut self,
+        action: Action,
+        cell: Cell,
+        previous: Vec<(Cell, u16)>,
+        counters: [u32; 3],
+    ) {
+        let changes: Vec<_> = previous
+            .into_iter()
+            .map(|(c, value)| (c, value & 0xc000, self.board[c] & 0xc000))
+            .filter(|(_, before, after)| before != after)
+            .collect();
+
+        if changes.is_empty() && counters == self.counters() {
+            return;
+        }
 
-            if set.is_empty() {
-                break;
+        self.bbbv.apply(&changes, false);
+        self.move_counts.add(action);
+        self.moves.push(Move {
+            action,
+            cell,
+            changes,
+            counters: (counters, self.counters()),
+        });
+        self.undone.clear();
+    }
+
+    /// Uncovers a cell and, breadth first, all cells connected to it through cells without neighbouring mines.
+    /// Returns the uncovered cells together with their previous values.
+    fn uncover_recursively(&mut self, cell: Cell) -> Vec<(Cell, u16)> {
+        let mut uncovered = Vec::new();
+        if !Self::is_uncovered(self.board[cell]) {
+            uncovered.push((cell, self.board[cell]));
+        }
+        self.board[cell] |= 0xc000;
+
+        // cells are uncovered when they are queued, so the uncovered state marks them as visited
+        let mut queue = VecDeque::from([cell]);
+        while let Some(c) = queue.pop_front() {
+            if Self::mines(self.board[c]) != 0 {
+                continue;
+            }
+
+            for n in self.layout.neighbors(c) {
+                if Self::is_covered(self.board[n]) {
+                    uncovered.push((n, self.board[n]));
+                    self.board[n] |= 0xc000;
+                    queue.push_back(n);
+                }
             }
         }
+
+        uncovered
     }
 
-    /// Randomly places the given number of mines on the board.
-    fn place_mines(&mut self, number: u32) {
-        let mut rng = rand::thread_rng();
-        let (s6, s5, s4, s3, s2, s1) = self.board.dim();
-        let mut number = number;
+    /// Stores the number of neighbouring mines in every empty cell.
+    fn count_mines(&mut self) {
+        for cell in 0..self.board.len() {
+            if !Self::is_empty(self.board[cell]) {
+                continue;
+            }
 
-        while number > 0 {
-            let x6: usize = rng.gen_range(0..s6);
-            let x5: usize = rng.gen_range(0..s5);
-            let x4: usize = rng.gen_range(0..s4);
-            let x3: usize = rng.gen_range(0..s3);
-            let x2: usize = rng.gen_range(0..s2);
-            let x1: usize = rng.gen_range(0..s1);
+            let mines = self
+                .neighbors(cell)
+                .filter(|n| !Self::is_empty(self.board[*n]))
+                .count() as u16;
 
-            if Self::is_empty(self.board[[x6, x5, x4, x3, x2, x1]]) {
-                self.board[[x6, x5, x4, x3, x2, x1]] = 0x2001;
-                number -= 1;
+            self.board[cell] = (self.board[cell] & 0xe000) | mines;
+        }
+    }
 
-                // increment the values of the neighbouring cells
-                for n in self.neighbors((x6, x5, x4, x3, x2, x1)) {
-                    let (x6, x5, x4, x3, x2, x1) = n;
+    /// Places the mines if this hasn't happened yet, keeping the safe zone around cell free of mines.
+    fn place_mines_around(&mut self, cell: Cell) {
+        if self.mines_placed {
+            return;
+        }
 
-                    if Self::is_empty(self.board[[x6, x5, x4, x3, x2, x1]]) {
-                        self.board[[x6, x5, x4, x3, x2, x1]] += 1;
+        // the no-guess generator needs a safe cell to start from
+        let mut start = Vec::new();
+        if self.rules.safe_start != SafeStart::Off || self.rules.generator != Generator::Random {
+            start.push(cell);
+        }
+        if self.rules.safe_start == SafeStart::Neighbourhood {
+            start.extend(self.neighbors(cell));
+        }
+
+        // the safe regions never contain mines, the safe start shrinks to the first cell if there is not enough
+        // space for the mines, `Board::validate` ensures that the mines fit around the first cell
+        let regions = self.rules.safe_regions.mask(self.size());
+        let mut safe_zone = regions.clone();
+        for c in &start {
+            safe_zone[*c] = true;
+        }
+        if safe_zone.iter().filter(|s| !**s).count() < self.mines_total as usize {
+            safe_zone = regions;
+            if let Some(c) = start.first() {
+                safe_zone[*c] = true;
+            }
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+
+        match self.rules.generator {
+            Generator::Random => self.place_mines(self.mines_total, &safe_zone, &mut rng),
+            Generator::NoGuess(attempts) => {
+                // the board that could be solved the furthest and the number of uncovered cells
+                let mut best: Option<(Vec<u16>, usize)> = None;
+                let state = self.board.clone();
+
+                for _ in 0..attempts {
+                    self.board.copy_from_slice(&state);
+                    self.place_mines(self.mines_total, &safe_zone, &mut rng);
+
+                    let (solved, uncovered) = solver::solve_from(self, cell);
+                    if solved {
+                        self.solvable = Some(true);
+                        return;
+                    }
+
+                    if best.as_ref().is_none_or(|(_, u)| uncovered > *u) {
+                        best = Some((self.board.clone(), uncovered));
                     }
                 }
+
+                if let Some((board, _)) = best {
+                    self.board = board;
+                    self.bbbv = Bbbv::new(self);
+                }
+                self.solvable = Some(false);
             }
         }
     }
+
+    /// Replaces the mines by the given cells, e.g. to recreate a recorded board.
+    pub fn place_mines_at(&mut self, mines: &[Cell]) {
+        for value in self.board.iter_mut() {
+            *value &= 0xc000;
+        }
+        for cell in mines {
+            self.board[*cell] |= 0x2001;
+        }
+
+        self.mines_total = self.board.iter().filter(|v| !Self::is_empty(**v)).count() as u32;
+        self.mines_placed = true;
+        self.count_mines();
+        self.bbbv = Bbbv::new(self);
+    }
+
+    /// Randomly places the given number of mines on the board, excluding the cells in safe_zone.
+    /// Every choice of cells is equally likely, the cells are drawn by a partial Fisher–Yates shuffle.
+    fn place_mines(&mut self, number: u32, safe_zone: &[bool], rng: &mut ChaCha8Rng) {
+        let mut candidates: Vec<Cell> = (0..self.board.len())
+            .filter(|c| Self::is_empty(self.board[*c]) && !safe_zone[*c])
+            .collect();
+        let (mines, _) = candidates.partial_shuffle(rng, number as usize);
+
+        for cell in mines {
+            // keep the state of cells that have been flagged or marked before the mines were placed
+            self.board[*cell] = (self.board[*cell] & 0xc000) | 0x2001;
+        }
+        self.mines_placed = true;
+
+        // the neighbourhood is not necessarily symmetric at mirrored edges, so the mines are counted for each cell
+        self.count_mines();
+        self.bbbv = Bbbv::new(self);
+    }
 }
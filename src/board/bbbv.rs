@@ -0,0 +1,112 @@
+use super::{Board, Cell};
+
+/// Marks a cell that doesn't belong to an opening, uncovering it solves a 3BV of its own.
+const ISOLATED: u32 = u32::MAX;
+/// Marks a mine or a cell on the border of an opening, uncovering it doesn't solve anything.
+const NONE: u32 = u32::MAX - 1;
+
+/// The 3BV of a board, computed once when the mines are placed. The solved 3BV is updated from the cells whose
+/// state is changed by each move, see `Board::bbbv`.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Bbbv {
+    /// The opening each cell without neighbouring mines belongs to, `ISOLATED` or `NONE` for all other cells
+    openings: Vec<u32>,
+    /// The number of uncovered cells of each opening without neighbouring mines
+    uncovered: Vec<u32>,
+    pub total: u32,
+    pub solved: u32,
+}
+
+impl Bbbv {
+    /// Finds the openings of a board whose mines have been placed and counts the solved 3BV.
+    pub fn new(board: &Board) -> Self {
+        let zero = |value: u16| Board::is_empty(value) && Board::mines(value) == 0;
+        let mut openings: Vec<u32> = board
+            .board
+            .iter()
+            .map(|v| if Board::is_empty(*v) { ISOLATED } else { NONE })
+            .collect();
+        let mut count = 0;
+
+        for cell in 0..board.board.len() {
+            if openings[cell] != ISOLATED || !zero(board.board[cell]) {
+                continue;
+            }
+
+            openings[cell] = count;
+            let mut stack = vec![cell];
+            while let Some(c) = stack.pop() {
+                for n in board.neighbors(c) {
+                    if openings[n] != ISOLATED {
+                        continue;
+                    }
+                    if zero(board.board[n]) {
+                        openings[n] = count;
+                        stack.push(n);
+                    } else {
+                        openings[n] = NONE;
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        let isolated = openings.iter().filter(|o| **o == ISOLATED).count() as u32;
+        let mut bbbv = Self {
+            openings,
+            uncovered: vec![0; count as usize],
+            total: count + isolated,
+            solved: 0,
+        };
+        for (cell, value) in board.board.iter().enumerate() {
+            if Board::is_uncovered(*value) {
+                bbbv.update(cell, true);
+            }
+        }
+
+        bbbv
+    }
+
+    /// Updates the solved 3BV after the changes of a move (see `Move::changes`) have been applied, or reverted by
+    /// undoing it.
+    pub fn apply(&mut self, changes: &[(Cell, u16, u16)], revert: bool) {
+        for (cell, before, after) in changes {
+            let (before, after) = if revert {
+                (after, before)
+            } else {
+                (before, after)
+            };
+            if (*before == 0xc000) != (*after == 0xc000) {
+                self.update(*cell, *after == 0xc000);
+            }
+        }
+    }
+
+    /// Updates the solved 3BV after a cell has been uncovered, or covered again by undoing a move.
+    fn update(&mut self, cell: Cell, uncovered: bool) {
+        let opening = match self.openings.get(cell) {
+            // the mines haven't been placed
+            None | Some(&NONE) => return,
+            Some(&ISOLATED) => None,
+            Some(opening) => Some(*opening as usize),
+        };
+
+        let first = match opening {
+            Some(o) if uncovered => {
+                self.uncovered[o] += 1;
+                self.uncovered[o] == 1
+            }
+            Some(o) => {
+                self.uncovered[o] -= 1;
+                self.uncovered[o] == 0
+            }
+            None => true,
+        };
+
+        if first && uncovered {
+            self.solved += 1;
+        } else if first {
+            self.solved -= 1;
+        }
+    }
+}
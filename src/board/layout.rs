@@ -0,0 +1,167 @@
+use super::{Boundary, Cell, Rules, MAX_DIMENSIONS};
+
+/// The shape of a board and the offsets of the neighbouring cells, precomputed so that the neighbours of a cell
+/// can be iterated without allocating.
+#[derive(Clone, Debug)]
+pub(super) struct Layout {
+    /// Size of the board as (x₁, x₂, …, xₙ)
+    pub size: Vec<usize>,
+    /// Distance in `Board::board` between neighbouring cells along each axis
+    pub strides: Vec<usize>,
+    /// The boundary of each axis
+    boundaries: Vec<Boundary>,
+    /// Offsets of the neighbouring cells as (x₁, x₂, …, xₙ)
+    offsets: Vec<Vec<isize>>,
+    /// Each offset as a distance in `Board::board`, valid for cells whose neighbours don't cross an edge
+    deltas: Vec<isize>,
+    /// Largest absolute offset along each axis, a cell at least this far from all edges doesn't see beyond them
+    reach: Vec<usize>,
+}
+
+impl Layout {
+    /// Precomputes the layout of a board of the given size, see `Board::new`.
+    pub fn new(size: &[usize], rules: &Rules) -> Self {
+        let strides: Vec<usize> = size
+            .iter()
+            .scan(1, |stride, x| {
+                let s = *stride;
+                *stride *= x;
+                Some(s)
+            })
+            .collect();
+        let offsets = rules.neighbourhood.offsets(size);
+
+        let deltas = offsets
+            .iter()
+            .map(|o| o.iter().zip(&strides).map(|(x, s)| x * *s as isize).sum())
+            .collect();
+        let reach = (0..size.len())
+            .map(|i| {
+                offsets
+                    .iter()
+                    .map(|o| o[i].unsigned_abs())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        Self {
+            size: size.to_vec(),
+            strides,
+            boundaries: (0..size.len()).map(|i| rules.topology.axis(i)).collect(),
+            offsets,
+            deltas,
+            reach,
+        }
+    }
+
+    /// Returns the number of cells.
+    pub fn len(&self) -> usize {
+        self.size.iter().product()
+    }
+
+    /// Returns the index of the cell at the given coordinates, see `Board::index`.
+    pub fn index(&self, coordinates: &[usize]) -> Option<Cell> {
+        if coordinates.len() > self.size.len() {
+            return None;
+        }
+
+        let mut index = 0;
+        for ((x, size), stride) in coordinates.iter().zip(&self.size).zip(&self.strides) {
+            if x >= size {
+                return None;
+            }
+            index += x * stride;
+        }
+        Some(index)
+    }
+
+    /// Returns the coordinates (x₁, x₂, …, xₙ) of a cell.
+    pub fn coordinates(&self, cell: Cell) -> Vec<usize> {
+        self.size
+            .iter()
+            .zip(&self.strides)
+            .map(|(size, stride)| cell / stride % size)
+            .collect()
+    }
+
+    /// Returns an iterator over the neighbours of a cell, see `Board::neighbors`.
+    pub fn neighbors(&self, cell: Cell) -> Neighbors<'_> {
+        let mut coordinates = [0; MAX_DIMENSIONS];
+        let mut edges = 0;
+
+        for (i, x) in coordinates.iter_mut().enumerate().take(self.size.len()) {
+            *x = cell / self.strides[i] % self.size[i];
+            if *x < self.reach[i] || *x + self.reach[i] >= self.size[i] {
+                edges |= 1 << i;
+            }
+        }
+
+        Neighbors {
+            layout: self,
+            cell,
+            coordinates,
+            edges,
+            offset: 0,
+        }
+    }
+}
+
+/// Iterator over the neighbours of a cell, returned by `Board::neighbors`.
+#[derive(Clone, Debug)]
+pub struct Neighbors<'a> {
+    layout: &'a Layout,
+    cell: Cell,
+    coordinates: [usize; MAX_DIMENSIONS],
+    /// The axes along which a neighbour may lie beyond an edge as a bit set, the offsets along all other axes can
+    /// be applied to the index directly
+    edges: u16,
+    /// Index of the next offset in `layout.offsets`
+    offset: usize,
+}
+
+impl Neighbors<'_> {
+    /// Applies the offset with the given index, returns None if the neighbour lies outside of the board.
+    fn apply(&self, offset: usize) -> Option<Cell> {
+        let layout = self.layout;
+        let mut n = self.cell as isize + layout.deltas[offset];
+
+        // correct the axes along which the offset crosses an edge
+        let mut edges = self.edges;
+        while edges != 0 {
+            let i = edges.trailing_zeros() as usize;
+            edges &= edges - 1;
+
+            let x = self.coordinates[i] as isize + layout.offsets[offset][i];
+            if x < 0 || x >= layout.size[i] as isize {
+                let wrapped = layout.boundaries[i].wrap(x, layout.size[i])? as isize;
+                n += (wrapped - x) * layout.strides[i] as isize;
+            }
+        }
+
+        Some(n as Cell)
+    }
+}
+
+impl Iterator for Neighbors<'_> {
+    type Item = Cell;
+
+    fn next(&mut self) -> Option<Cell> {
+        while self.offset < self.layout.offsets.len() {
+            let n = self.apply(self.offset);
+            self.offset += 1;
+
+            // a wrapped offset can lead back to the cell itself
+            match n {
+                Some(n) if n != self.cell => return Some(n),
+                _ => continue,
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.layout.offsets.len() - self.offset))
+    }
+}
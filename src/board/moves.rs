@@ -0,0 +1,93 @@
+use super::Cell;
+use serde::{Deserialize, Serialize};
+
+/// An action of the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Uncover,
+    Flag,
+    Mark,
+    Cheat,
+    Chord,
+}
+
+/// Something the player did to the board, a game is restored by executing its commands again, see
+/// `Board::execute`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Command {
+    Uncover(Cell),
+    Flag(Cell),
+    Mark(Cell),
+    Cheat(Cell),
+    Chord(Cell),
+    Undo,
+    Redo,
+}
+
+impl Command {
+    /// Returns the cell the command is applied to, None for undo and redo.
+    pub const fn cell(&self) -> Option<Cell> {
+        match self {
+            Self::Uncover(cell)
+            | Self::Flag(cell)
+            | Self::Mark(cell)
+            | Self::Cheat(cell)
+            | Self::Chord(cell) => Some(*cell),
+            Self::Undo | Self::Redo => None,
+        }
+    }
+}
+
+/// The result of uncovering cells, see `Board::uncover_cell` and `Board::chord_cell`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Uncovered {
+    /// No mine has been uncovered
+    Safe,
+    /// A mine would have been uncovered in practice mode, so nothing has been uncovered and the move counts as
+    /// undone
+    Undone,
+    /// A mine has been uncovered, which lost the game
+    Lost,
+}
+
+/// An action together with its effect on the board, used to undo and redo it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub action: Action,
+    /// The cell the action has been applied to, see `Board::index`
+    pub cell: Cell,
+    /// The cells whose state changed, together with their states (bits 15-14) before and after the move
+    pub(crate) changes: Vec<(Cell, u16, u16)>,
+    /// `mines_flagged`, `mines_marked` and `cheats_remaining` before and after the move
+    pub(super) counters: ([u32; 3], [u32; 3]),
+}
+
+/// Number of moves of each action, including moves that have been undone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MoveCounts {
+    pub uncovers: u32,
+    pub chords: u32,
+    pub flags: u32,
+    pub marks: u32,
+    pub cheats: u32,
+}
+
+impl MoveCounts {
+    /// Counts a move of the given action.
+    pub fn add(&mut self, action: Action) {
+        match action {
+            Action::Uncover => self.uncovers += 1,
+            Action::Chord => self.chords += 1,
+            Action::Flag => self.flags += 1,
+            Action::Mark => self.marks += 1,
+            Action::Cheat => self.cheats += 1,
+        }
+    }
+
+    /// Returns the total number of moves.
+    pub const fn total(&self) -> u32 {
+        self.uncovers + self.chords + self.flags + self.marks + self.cheats
+    }
+}
@@ -0,0 +1,223 @@
+use super::MAX_DIMENSIONS;
+use std::{fmt, str::FromStr};
+
+/// The largest number of neighbours a cell may have, limited by the 13 bits used to store the number of mines.
+pub const MAX_NEIGHBORS: usize = 0x1fff;
+
+/// Defines which cells are adjacent to a cell.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum Neighbourhood {
+    /// Cells that differ by 1 along a single axis, i.e. up to 2n neighbours on a board having n axes.
+    #[default]
+    VonNeumann,
+    /// Cells that differ by at most 1 along every axis, i.e. up to 3ⁿ - 1 neighbours on a board having n axes.
+    Moore,
+    /// Cells within the given Manhattan distance.
+    Manhattan(usize),
+    /// Cells within the given Chebyshev distance.
+    Chebyshev(usize),
+    /// User-supplied offsets given as (x₁, x₂, …), missing coordinates are 0.
+    /// The negated offsets are added automatically.
+    Custom(Vec<Vec<isize>>),
+}
+
+impl Neighbourhood {
+    /// Returns the number of neighbours a cell has on an unbounded board of the given size,
+    /// axes of size 1 are ignored.
+    pub fn max_neighbors(&self, size: &[usize]) -> usize {
+        let axes = Self::active_axes(size).iter().filter(|a| **a).count() as u32;
+
+        match self {
+            Self::VonNeumann => 2 * axes as usize,
+            Self::Moore => 3usize.pow(axes) - 1,
+            Self::Manhattan(r) => {
+                // the number of points having exactly i nonzero coordinates is 2^i * C(axes, i) * C(r, i)
+                let mut total: usize = 0;
+                for i in 1..=axes as usize {
+                    let points = binomial(axes as usize, i)
+                        .saturating_mul(binomial(*r, i))
+                        .saturating_mul(1 << i);
+                    total = total.saturating_add(points);
+                }
+                total
+            }
+            Self::Chebyshev(r) => r
+                .saturating_mul(2)
+                .saturating_add(1)
+                .saturating_pow(axes)
+                .saturating_sub(1),
+            Self::Custom(_) => self.offsets(size).len(),
+        }
+    }
+
+    /// Returns an error message if a cell could have too many neighbours to be stored.
+    pub fn validate(&self, size: &[usize]) -> Result<(), String> {
+        let neighbors = self.max_neighbors(size);
+
+        if neighbors > MAX_NEIGHBORS {
+            Err(format!(
+                "The neighbourhood \"{}\" has {} cells on this board, at most {} are supported",
+                self, neighbors, MAX_NEIGHBORS
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the offsets of all neighbours on a board of the given size as (x₁, x₂, …, xₙ),
+    /// offsets along axes of size 1 or beyond the axes of the board are omitted.
+    pub fn offsets(&self, size: &[usize]) -> Vec<Vec<isize>> {
+        let active = Self::active_axes(size);
+
+        let mut result = match self {
+            Self::VonNeumann => Self::ball(active, 1, |o| o.iter().map(|x| x.abs()).sum()),
+            Self::Moore => Self::ball(active, 1, |o| o.iter().map(|x| x.abs()).max().unwrap()),
+            Self::Manhattan(r) => Self::ball(active, *r, |o| o.iter().map(|x| x.abs()).sum()),
+            Self::Chebyshev(r) => {
+                Self::ball(active, *r, |o| o.iter().map(|x| x.abs()).max().unwrap())
+            }
+            Self::Custom(stencil) => stencil
+                .iter()
+                .filter(|o| o.iter().skip(size.len()).all(|x| *x == 0))
+                .flat_map(|o| {
+                    let mut o = o.clone();
+                    o.resize(size.len(), 0);
+                    vec![o.iter().map(|x| -x).collect(), o]
+                })
+                .filter(|o: &Vec<isize>| o.iter().zip(&active).all(|(x, a)| *a || *x == 0))
+                .collect(),
+        };
+
+        result.retain(|o| o.iter().any(|x| *x != 0));
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    /// Returns which axes have a size greater than 1.
+    fn active_axes(size: &[usize]) -> Vec<bool> {
+        size.iter().map(|x| *x > 1).collect()
+    }
+
+    /// Returns all offsets along the active axes whose distance (according to norm) is at most radius.
+    fn ball(active: Vec<bool>, radius: usize, norm: fn(&[isize]) -> isize) -> Vec<Vec<isize>> {
+        let r = radius as isize;
+        let mut result = vec![vec![0; active.len()]];
+
+        for (axis, is_active) in active.iter().enumerate() {
+            if !is_active {
+                continue;
+            }
+
+            let mut extended = Vec::new();
+            for offset in &result {
+                for d in -r..=r {
+                    let mut o = offset.clone();
+                    o[axis] = d;
+                    if norm(&o) <= r {
+                        extended.push(o);
+                    }
+                }
+            }
+            result = extended;
+        }
+
+        result
+    }
+}
+
+impl fmt::Display for Neighbourhood {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::VonNeumann => write!(f, "von-neumann"),
+            Self::Moore => write!(f, "moore"),
+            Self::Manhattan(r) => write!(f, "manhattan:{}", r),
+            Self::Chebyshev(r) => write!(f, "chebyshev:{}", r),
+            Self::Custom(stencil) => {
+                let offsets: Vec<String> = stencil
+                    .iter()
+                    .map(|o| {
+                        o.iter()
+                            .map(|x| x.to_string())
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    })
+                    .collect();
+                write!(f, "custom:{}", offsets.join(";"))
+            }
+        }
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    /// Parses "von-neumann", "moore", "manhattan:r", "chebyshev:r" or "custom:x₁,x₂,…;x₁,x₂,…",
+    /// missing coordinates of custom offsets are 0.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let (name, argument) = match s.find(':') {
+            Some(i) => (&s[..i], Some(s[i + 1..].trim())),
+            None => (s.as_str(), None),
+        };
+
+        let radius = || -> Result<usize, String> {
+            argument
+                .ok_or(format!(
+                    "\"{}\" requires a radius, e.g. \"{}:2\"",
+                    name, name
+                ))?
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid radius for \"{}\"", name))
+        };
+
+        match name.trim() {
+            "von-neumann" | "vonneumann" | "von neumann" => Ok(Self::VonNeumann),
+            "moore" => Ok(Self::Moore),
+            "manhattan" => Ok(Self::Manhattan(radius()?)),
+            "chebyshev" => Ok(Self::Chebyshev(radius()?)),
+            "custom" => {
+                let mut stencil = Vec::new();
+
+                for offset in argument.unwrap_or("").split(';') {
+                    if offset.trim().is_empty() {
+                        continue;
+                    }
+
+                    let o = offset
+                        .split(',')
+                        .map(|x| x.trim().parse())
+                        .collect::<Result<Vec<isize>, _>>()
+                        .map_err(|_| format!("Invalid offset \"{}\"", offset))?;
+                    if o.len() > MAX_DIMENSIONS {
+                        return Err(format!(
+                            "The offset \"{}\" has more than {} coordinates",
+                            offset, MAX_DIMENSIONS
+                        ));
+                    }
+                    stencil.push(o);
+                }
+
+                if stencil.is_empty() {
+                    Err("A custom neighbourhood requires at least one offset".to_string())
+                } else {
+                    Ok(Self::Custom(stencil))
+                }
+            }
+            _ => Err(format!("Unknown neighbourhood \"{}\"", name)),
+        }
+    }
+}
+
+/// Returns n choose k, saturating on overflow.
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+
+    let mut result: usize = 1;
+    for i in 0..k {
+        result = result.saturating_mul(n - i) / (i + 1);
+    }
+    result
+}
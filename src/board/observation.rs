@@ -0,0 +1,95 @@
+use super::{Board, Cell, Neighbors, Rules};
+
+/// The state of a cell as far as the player knows it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CellState {
+    Covered,
+    Flagged,
+    Marked,
+    /// An uncovered cell and the number of mines in its neighbourhood
+    Uncovered(u16),
+    /// The mine that has been uncovered, which lost the game
+    Exploded,
+}
+
+/// A read-only view of a board that only reveals what the player may know, i.e. never where the mines are.
+#[derive(Clone, Copy)]
+pub struct Observation<'a> {
+    board: &'a Board,
+}
+
+impl<'a> Observation<'a> {
+    pub(super) const fn new(board: &'a Board) -> Self {
+        Self { board }
+    }
+
+    /// Returns the size of the board as (x₁, x₂, …, xₙ).
+    pub fn size(&self) -> &'a [usize] {
+        self.board.size()
+    }
+
+    /// Returns the cell at the given coordinates, see `Board::index`.
+    pub fn index(&self, coordinates: &[usize]) -> Option<Cell> {
+        self.board.index(coordinates)
+    }
+
+    /// Returns the coordinates (x₁, x₂, …, xₙ) of a cell.
+    pub fn coordinates(&self, cell: Cell) -> Vec<usize> {
+        self.board.coordinates(cell)
+    }
+
+    pub const fn rules(&self) -> &Rules {
+        &self.board.rules
+    }
+
+    pub const fn mines_total(&self) -> u32 {
+        self.board.mines_total
+    }
+
+    pub const fn mines_flagged(&self) -> u32 {
+        self.board.mines_flagged
+    }
+
+    pub const fn mines_marked(&self) -> u32 {
+        self.board.mines_marked
+    }
+
+    pub const fn cheats_remaining(&self) -> u32 {
+        self.board.cheats_remaining
+    }
+
+    /// Returns the mine that lost the game, if it has been lost.
+    pub const fn exploded(&self) -> Option<Cell> {
+        self.board.exploded
+    }
+
+    /// Returns the state of a cell.
+    ///
+    /// # Panics
+    /// Panics if the cell is outside of the board.
+    pub fn cell(&self, cell: Cell) -> CellState {
+        let value = self.board.board[cell];
+
+        if self.board.exploded == Some(cell) {
+            CellState::Exploded
+        } else if Board::is_flagged(value) {
+            CellState::Flagged
+        } else if Board::is_marked(value) {
+            CellState::Marked
+        } else if Board::is_uncovered(value) {
+            CellState::Uncovered(Board::mines(value))
+        } else {
+            CellState::Covered
+        }
+    }
+
+    /// Returns all cells and their states, x₁ varies fastest.
+    pub fn cells(&self) -> impl Iterator<Item = (Cell, CellState)> + '_ {
+        (0..self.board.board.len()).map(move |cell| (cell, self.cell(cell)))
+    }
+
+    /// Returns the neighbours of a cell, see `Board::neighbors`.
+    pub fn neighbors(&self, cell: Cell) -> Neighbors<'a> {
+        self.board.neighbors(cell)
+    }
+}
@@ -0,0 +1,153 @@
+use super::MAX_DIMENSIONS;
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+/// A box of cells given by a range of coordinates along each axis as (x₁, x₂, …), axes beyond the list are
+/// covered completely.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region(pub Vec<RangeInclusive<usize>>);
+
+impl Region {
+    /// Returns the range along each axis of a board having the given size.
+    fn ranges(&self, size: &[usize]) -> Vec<RangeInclusive<usize>> {
+        size.iter()
+            .enumerate()
+            .map(|(i, x)| self.0.get(i).cloned().unwrap_or(0..=x - 1))
+            .collect()
+    }
+}
+
+impl fmt::Display for Region {
+    /// Formats the region as a comma separated list of ranges like "2-4" or single coordinates.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranges: Vec<String> = self
+            .0
+            .iter()
+            .map(|r| {
+                if r.start() == r.end() {
+                    r.start().to_string()
+                } else {
+                    format!("{}-{}", r.start(), r.end())
+                }
+            })
+            .collect();
+        write!(f, "{}", ranges.join(","))
+    }
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |x: &str| {
+            x.trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid coordinate \"{}\" in the safe region \"{}\"", x, s))
+        };
+
+        let ranges = s
+            .split(',')
+            .map(|r| match r.split_once('-') {
+                Some((start, end)) => Ok(parse(start)?..=parse(end)?),
+                None => parse(r).map(|x| x..=x),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        if ranges.len() > MAX_DIMENSIONS {
+            Err(format!(
+                "A safe region can have at most {} axes",
+                MAX_DIMENSIONS
+            ))
+        } else if ranges.iter().any(|r| r.is_empty()) {
+            Err(format!("The safe region \"{}\" is empty", s))
+        } else {
+            Ok(Self(ranges))
+        }
+    }
+}
+
+/// Regions of the board that never contain mines, in addition to the safe start.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct SafeRegions(pub Vec<Region>);
+
+impl SafeRegions {
+    /// Returns an error message if a region doesn't lie on a board having the given size.
+    pub fn validate(&self, size: &[usize]) -> Result<(), String> {
+        for region in &self.0 {
+            if region.0.len() > size.len() {
+                return Err(format!(
+                    "The safe region \"{}\" has more axes than the board",
+                    region
+                ));
+            }
+            if region.0.iter().zip(size).any(|(r, x)| r.end() >= x) {
+                return Err(format!(
+                    "The safe region \"{}\" is outside of the board",
+                    region
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether each cell of a board having the given size lies in a region, the cells are ordered like
+    /// `Board::board`. The regions have to be valid, see `SafeRegions::validate`.
+    pub fn mask(&self, size: &[usize]) -> Vec<bool> {
+        let mut mask = vec![false; size.iter().product()];
+
+        for region in &self.0 {
+            let ranges = region.ranges(size);
+            let mut coordinates: Vec<usize> = ranges.iter().map(|r| *r.start()).collect();
+
+            // visit every cell of the box, x₁ varies fastest
+            'cells: loop {
+                let cell = coordinates
+                    .iter()
+                    .zip(size)
+                    .rev()
+                    .fold(0, |cell, (x, size)| cell * size + x);
+                mask[cell] = true;
+
+                for (x, range) in coordinates.iter_mut().zip(&ranges) {
+                    if *x < *range.end() {
+                        *x += 1;
+                        continue 'cells;
+                    }
+                    *x = *range.start();
+                }
+                break;
+            }
+        }
+
+        mask
+    }
+}
+
+impl fmt::Display for SafeRegions {
+    /// Formats the regions separated by semicolons, or "none".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "none");
+        }
+
+        let regions: Vec<String> = self.0.iter().map(|r| r.to_string()).collect();
+        write!(f, "{}", regions.join(";"))
+    }
+}
+
+impl FromStr for SafeRegions {
+    type Err = String;
+
+    /// Parses regions separated by semicolons, each given as a comma separated list of ranges ordered
+    /// (x₁, x₂, …) like "0-2,0-2;5,3", an empty string or "none" results in no regions.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "none" => Ok(Self::default()),
+            _ => s
+                .split(';')
+                .map(|r| r.parse())
+                .collect::<Result<_, _>>()
+                .map(Self),
+        }
+    }
+}
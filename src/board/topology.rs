@@ -0,0 +1,120 @@
+use super::MAX_DIMENSIONS;
+use std::{fmt, str::FromStr};
+
+/// Defines what lies beyond the edges of a single axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    /// There are no cells beyond the edges.
+    Bounded,
+    /// The axis wraps around, i.e. the first and last cells are adjacent.
+    Periodic,
+    /// The cells beyond an edge are reflections of the cells inside, i.e. a cell next to an edge
+    /// sees its inner neighbours twice.
+    Mirrored,
+}
+
+impl Boundary {
+    /// Maps the coordinate x onto an axis of the given size, returns None if it lies outside of the board.
+    pub fn wrap(self, x: isize, size: usize) -> Option<usize> {
+        let s = size as isize;
+
+        match self {
+            Self::Bounded => {
+                if x >= 0 && x < s {
+                    Some(x as usize)
+                } else {
+                    None
+                }
+            }
+            Self::Periodic => Some(x.rem_euclid(s) as usize),
+            Self::Mirrored => {
+                if s == 1 {
+                    return Some(0);
+                }
+
+                let period = 2 * (s - 1);
+                let x = x.rem_euclid(period);
+                Some(if x < s { x } else { period - x } as usize)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Boundary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bounded => write!(f, "bounded"),
+            Self::Periodic => write!(f, "periodic"),
+            Self::Mirrored => write!(f, "mirrored"),
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "bounded" | "b" => Ok(Self::Bounded),
+            "periodic" | "torus" | "p" => Ok(Self::Periodic),
+            "mirrored" | "m" => Ok(Self::Mirrored),
+            other => Err(format!("Unknown topology \"{}\"", other)),
+        }
+    }
+}
+
+/// The boundaries of the axes as (x₁, x₂, …), axes beyond the list are bounded.
+/// A single boundary applies to all axes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Topology(pub Vec<Boundary>);
+
+impl Topology {
+    /// Creates a topology using the same boundary for all axes.
+    pub fn uniform(boundary: Boundary) -> Self {
+        Self(vec![boundary])
+    }
+
+    /// Returns the boundary of the given axis, 0 being x₁.
+    pub fn axis(&self, axis: usize) -> Boundary {
+        match self.0.as_slice() {
+            [boundary] => *boundary,
+            boundaries => boundaries.get(axis).copied().unwrap_or(Boundary::Bounded),
+        }
+    }
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Self::uniform(Boundary::Bounded)
+    }
+}
+
+impl fmt::Display for Topology {
+    /// Formats the topology as a single boundary or as a list ordered (x₁, x₂, …).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let boundaries: Vec<String> = self.0.iter().map(|b| b.to_string()).collect();
+        write!(f, "{}", boundaries.join(","))
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    /// Parses a single boundary used for all axes, or a comma separated list ordered (x₁, x₂, …),
+    /// missing axes are bounded.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let boundaries = s
+            .split(',')
+            .map(|b| b.parse())
+            .collect::<Result<Vec<Boundary>, String>>()?;
+
+        if boundaries.len() > MAX_DIMENSIONS {
+            Err(format!(
+                "A topology can have at most {} axes",
+                MAX_DIMENSIONS
+            ))
+        } else {
+            Ok(Self(boundaries))
+        }
+    }
+}
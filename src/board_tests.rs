@@ -1,7 +1,12 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod board_tests {
 
-    use super::super::Board;
+    use super::super::{
+        Board, Boundary, Neighbourhood, Region, Rules, SafeRegions, SafeStart, Topology, Uncovered,
+        WinCondition, MAX_CELLS, MAX_DIMENSIONS,
+    };
+    use std::collections::HashMap;
 
     #[test]
     fn test_is_covered() {
@@ -50,4 +55,699 @@ mod board_tests {
         assert_eq!(Board::mines(0b0000_0000_0000_0000), 0b0000_0000_0000_0000);
         assert_eq!(Board::mines(0b1110_0000_0000_0000), 0b0000_0000_0000_0000);
     }
+
+    fn rules(neighbourhood: Neighbourhood, boundary: Boundary) -> Rules {
+        Rules {
+            neighbourhood,
+            topology: Topology::uniform(boundary),
+            safe_start: SafeStart::Off,
+            ..Rules::default()
+        }
+    }
+
+    #[test]
+    fn test_neighbors_count() {
+        let size = [3, 3, 3];
+        let center = 13;
+        let corner = 0;
+
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::VonNeumann, Boundary::Bounded),
+            None,
+        );
+        assert_eq!(board.neighbors(center).count(), 6);
+        assert_eq!(board.neighbors(corner).count(), 3);
+
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::Moore, Boundary::Bounded),
+            None,
+        );
+        assert_eq!(board.neighbors(center).count(), 26);
+        assert_eq!(board.neighbors(corner).count(), 7);
+
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::Manhattan(2), Boundary::Bounded),
+            None,
+        );
+        assert_eq!(board.neighbors(center).count(), 18);
+
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::Chebyshev(1), Boundary::Bounded),
+            None,
+        );
+        assert_eq!(board.neighbors(center).count(), 26);
+    }
+
+    #[test]
+    fn test_dimensions() {
+        // 1D
+        let board = Board::new(&[5], 0, 0, Rules::default(), None);
+        assert!(board.neighbors(0).eq([1]));
+        assert_eq!(board.neighbors(2).count(), 2);
+
+        // 8D
+        let size = [3, 2, 2, 2, 2, 2, 2, 3];
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::Moore, Boundary::Periodic),
+            None,
+        );
+        assert_eq!(board.board.len(), 576);
+        assert_eq!(board.neighbors(0).count(), 3usize.pow(8) - 1);
+
+        let cell = board.index(&[2, 1, 0, 1, 0, 1, 0, 2]).unwrap();
+        assert_eq!(board.coordinates(cell), [2, 1, 0, 1, 0, 1, 0, 2]);
+        assert_eq!(board.index(&[2, 1]), board.index(&[2, 1, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(board.index(&[3]), None);
+        assert_eq!(board.index(&[0; 9]), None);
+    }
+
+    #[test]
+    fn test_max_neighbors() {
+        let size = [3; 6];
+        assert_eq!(Neighbourhood::VonNeumann.max_neighbors(&size), 12);
+        assert_eq!(Neighbourhood::Moore.max_neighbors(&size), 728);
+        assert_eq!(Neighbourhood::Chebyshev(2).max_neighbors(&size), 15624);
+
+        for n in [
+            Neighbourhood::VonNeumann,
+            Neighbourhood::Moore,
+            Neighbourhood::Manhattan(3),
+            Neighbourhood::Chebyshev(1),
+        ] {
+            assert_eq!(n.max_neighbors(&size), n.offsets(&size).len());
+        }
+
+        assert!(Neighbourhood::Chebyshev(2).validate(&size).is_err());
+        assert!(Neighbourhood::Chebyshev(2).validate(&[5, 5, 5]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_neighbourhood() {
+        assert_eq!("moore".parse(), Ok(Neighbourhood::Moore));
+        assert_eq!("Von-Neumann".parse(), Ok(Neighbourhood::VonNeumann));
+        assert_eq!("manhattan:2".parse(), Ok(Neighbourhood::Manhattan(2)));
+        assert_eq!(
+            "custom:1,1;2".parse(),
+            Ok(Neighbourhood::Custom(vec![vec![1, 1], vec![2]]))
+        );
+        assert!("chebyshev".parse::<Neighbourhood>().is_err());
+        assert!("hexagonal".parse::<Neighbourhood>().is_err());
+        assert!("custom:0,0,0,0,0,0,0,0,0,0,1"
+            .parse::<Neighbourhood>()
+            .is_err());
+
+        let custom = Neighbourhood::Custom(vec![vec![1, -1], vec![0, 0, 1]]);
+        assert_eq!(custom.to_string().parse(), Ok(custom.clone()));
+        // the offset along x₃ doesn't exist on a 2D board
+        assert_eq!(custom.offsets(&[3, 3]), vec![vec![-1, 1], vec![1, -1]]);
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(Boundary::Bounded.wrap(-1, 4), None);
+        assert_eq!(Boundary::Bounded.wrap(4, 4), None);
+        assert_eq!(Boundary::Periodic.wrap(-1, 4), Some(3));
+        assert_eq!(Boundary::Periodic.wrap(4, 4), Some(0));
+        assert_eq!(Boundary::Mirrored.wrap(-1, 4), Some(1));
+        assert_eq!(Boundary::Mirrored.wrap(4, 4), Some(2));
+        assert_eq!(Boundary::Mirrored.wrap(2, 4), Some(2));
+    }
+
+    #[test]
+    fn test_topology() {
+        let size = [4, 4];
+        let corner = 0;
+
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::Moore, Boundary::Periodic),
+            None,
+        );
+        assert_eq!(board.neighbors(corner).count(), 8);
+        assert!(board.neighbors(corner).any(|n| n == 15));
+
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            rules(Neighbourhood::VonNeumann, Boundary::Mirrored),
+            None,
+        );
+        assert_eq!(board.neighbors(corner).count(), 4);
+        assert_eq!(board.neighbors(corner).filter(|n| *n == 4).count(), 2);
+
+        let topology = Topology(vec![Boundary::Periodic, Boundary::Bounded]);
+        let board = Board::new(
+            &size,
+            0,
+            0,
+            Rules {
+                neighbourhood: Neighbourhood::VonNeumann,
+                topology: topology.clone(),
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        assert_eq!(board.neighbors(corner).count(), 3);
+        assert_eq!(topology.axis(7), Boundary::Bounded);
+
+        assert_eq!("p,b".parse(), Ok(topology.clone()));
+        assert_eq!(topology.to_string().parse(), Ok(topology));
+        assert_eq!("torus".parse(), Ok(Topology::uniform(Boundary::Periodic)));
+        assert_eq!(
+            Topology::uniform(Boundary::Mirrored).axis(9),
+            Boundary::Mirrored
+        );
+    }
+
+    #[test]
+    fn test_neighbors_at_edges() {
+        // the neighbours of cells next to an edge are computed through the coordinates, all others through the
+        // index, both have to agree with applying the offsets directly
+        let size = [6, 5, 1, 4];
+        for boundary in [Boundary::Bounded, Boundary::Periodic, Boundary::Mirrored] {
+            let neighbourhood = Neighbourhood::Chebyshev(2);
+            let board = Board::new(&size, 0, 0, rules(neighbourhood.clone(), boundary), None);
+
+            for cell in 0..board.board.len() {
+                let coordinates = board.coordinates(cell);
+                let mut expected: Vec<_> = neighbourhood
+                    .offsets(&size)
+                    .iter()
+                    .filter_map(|offset| {
+                        let n: Option<Vec<_>> = coordinates
+                            .iter()
+                            .zip(offset)
+                            .zip(&size)
+                            .map(|((x, o), s)| boundary.wrap(*x as isize + o, *s))
+                            .collect();
+                        board.index(&n?)
+                    })
+                    .filter(|n| *n != cell)
+                    .collect();
+                let mut neighbors: Vec<_> = board.neighbors(cell).collect();
+                expected.sort_unstable();
+                neighbors.sort_unstable();
+                assert_eq!(neighbors, expected, "{:?} {:?}", boundary, coordinates);
+            }
+        }
+    }
+
+    #[test]
+    fn test_uncover_recursively() {
+        // a board without mines is uncovered at once
+        let mut board = Board::new(
+            &[6; 6],
+            0,
+            0,
+            rules(Neighbourhood::VonNeumann, Boundary::Bounded),
+            None,
+        );
+        assert_eq!(
+            board.uncover_cell(board.index(&[3, 2, 1, 0, 5, 4]).unwrap()),
+            Uncovered::Safe
+        );
+        assert!(board.board.iter().all(|v| Board::is_uncovered(*v)));
+        assert!(board.is_won());
+
+        // the opening stops at the numbers next to a wall of mines and at flags: · · 1 * 1 · ·
+        let mut board = Board::new(
+            &[7, 3],
+            0,
+            0,
+            rules(Neighbourhood::VonNeumann, Boundary::Bounded),
+            None,
+        );
+        board.place_mines_at(&[3, 10, 17]);
+        board.flag_cell(1);
+        assert_eq!(board.uncover_cell(0), Uncovered::Safe);
+        let uncovered: Vec<_> = (0..board.board.len())
+            .filter(|c| Board::is_uncovered(board.board[*c]))
+            .map(|c| board.coordinates(c)[0])
+            .collect();
+        assert_eq!(uncovered, [0, 0, 1, 2, 0, 1, 2]);
+        assert!(Board::is_flagged(board.board[1]));
+    }
+
+    #[test]
+    fn test_mine_count() {
+        for boundary in [Boundary::Bounded, Boundary::Periodic, Boundary::Mirrored] {
+            let board = Board::new(
+                &[4, 4, 4],
+                20,
+                0,
+                rules(Neighbourhood::Moore, boundary),
+                None,
+            );
+            check_mine_count(&board);
+        }
+    }
+
+    fn check_mine_count(board: &Board) {
+        for (cell, value) in board.board.iter().enumerate() {
+            if Board::is_empty(*value) {
+                let mines = board
+                    .neighbors(cell)
+                    .filter(|n| !Board::is_empty(board.board[*n]))
+                    .count();
+                assert_eq!(Board::mines(*value) as usize, mines);
+            }
+        }
+    }
+
+    #[test]
+    fn test_safe_start() {
+        for _ in 0..20 {
+            let mut board = Board::new(
+                &[5, 5],
+                16,
+                0,
+                Rules {
+                    safe_start: SafeStart::Neighbourhood,
+                    ..Rules::default()
+                },
+                None,
+            );
+            assert!(!board.mines_placed);
+            assert_eq!(
+                board.board.iter().filter(|v| !Board::is_empty(**v)).count(),
+                0
+            );
+
+            assert_eq!(board.uncover_cell(12), Uncovered::Safe);
+            assert!(board.mines_placed);
+            assert_eq!(
+                board.board.iter().filter(|v| !Board::is_empty(**v)).count(),
+                16
+            );
+            for n in board.neighbors(12) {
+                assert!(Board::is_uncovered(board.board[n]));
+            }
+            check_mine_count(&board);
+        }
+
+        // the safe zone shrinks to a single cell if there is not enough space
+        let mut board = Board::new(
+            &[3, 3],
+            8,
+            0,
+            Rules {
+                safe_start: SafeStart::Neighbourhood,
+                ..Rules::default()
+            },
+            None,
+        );
+        assert_eq!(board.uncover_cell(4), Uncovered::Safe);
+        assert_eq!(
+            board.board.iter().filter(|v| !Board::is_empty(**v)).count(),
+            8
+        );
+    }
+
+    /// Returns the mines of the board as a bit set.
+    fn mine_set(board: &Board) -> usize {
+        (0..board.board.len())
+            .filter(|c| !Board::is_empty(board.board[*c]))
+            .fold(0, |set, c| set | 1 << c)
+    }
+
+    #[test]
+    fn test_uniform_placement() {
+        // 4 mines on a 3x3 board whose center is safe: each of the 70 choices of 4 out of 8 cells has to be
+        // equally likely, the chi-squared statistic with 69 degrees of freedom exceeds 111.3 with probability 0.001
+        let regions: SafeRegions = "1,1".parse().unwrap();
+        let mut counts = HashMap::new();
+        let games = 7000;
+
+        for seed in 0..games {
+            let mut board = Board::new(
+                &[3, 3],
+                4,
+                0,
+                Rules {
+                    safe_regions: regions.clone(),
+                    ..Rules::default()
+                },
+                Some(seed),
+            );
+            board.uncover_cell(4);
+            assert!(Board::is_empty(board.board[4]));
+            *counts.entry(mine_set(&board)).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts.len(), 70);
+        let expected = games as f64 / 70.0;
+        let chi_squared: f64 = counts
+            .values()
+            .map(|n| (*n as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_squared < 111.3, "{}", chi_squared);
+    }
+
+    #[test]
+    fn test_dense_placement() {
+        // all cells but the first uncovered one contain a mine
+        let size = [8, 8, 8, 8];
+        let mut board = Board::new(&size, 4095, 0, Rules::default(), Some(0));
+        let cell = board.index(&[1, 1, 1, 1]).unwrap();
+        assert_eq!(board.uncover_cell(cell), Uncovered::Safe);
+        assert_eq!(
+            board.board.iter().filter(|v| !Board::is_empty(**v)).count(),
+            4095
+        );
+        assert_eq!(Board::mines(board.board[cell]), 8);
+
+        // placed along with the board
+        let board = Board::new(
+            &size,
+            4095,
+            0,
+            rules(Neighbourhood::Moore, Boundary::Bounded),
+            None,
+        );
+        assert_eq!(
+            board.board.iter().filter(|v| Board::is_empty(**v)).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_safe_regions() {
+        let regions: SafeRegions = "0-1,2 ; 4".parse().unwrap();
+        assert_eq!(
+            regions,
+            SafeRegions(vec![Region(vec![0..=1, 2..=2]), Region(vec![4..=4])])
+        );
+        assert_eq!(regions.to_string(), "0-1,2;4");
+        assert_eq!(regions.to_string().parse(), Ok(regions.clone()));
+        assert_eq!("".parse(), Ok(SafeRegions::default()));
+        assert_eq!(
+            SafeRegions::default().to_string().parse(),
+            Ok(SafeRegions::default())
+        );
+        assert!("2-1".parse::<SafeRegions>().is_err());
+        assert!("a".parse::<SafeRegions>().is_err());
+        assert!("0,0,0,0,0,0,0,0,0,0,0".parse::<SafeRegions>().is_err());
+
+        // missing axes are covered completely
+        let mask = regions.mask(&[5, 3]);
+        let safe: Vec<_> = (0..15).filter(|c| mask[*c]).collect();
+        assert_eq!(safe, [4, 9, 10, 11, 14]);
+        assert!(regions.validate(&[5, 3]).is_ok());
+        assert!(regions.validate(&[4, 3]).is_err());
+        assert!(regions.validate(&[5]).is_err());
+
+        // the regions stay free of mines, the safe start shrinks to the first cell to make room
+        let rules = Rules {
+            safe_start: SafeStart::Neighbourhood,
+            safe_regions: regions,
+            ..Rules::default()
+        };
+        assert!(Board::validate(&[5, 3], 9, &rules).is_ok());
+        assert!(Board::validate(&[5, 3], 10, &rules).is_err());
+        for seed in 0..20 {
+            let mut board = Board::new(&[5, 3], 9, 0, rules.clone(), Some(seed));
+            assert_eq!(board.uncover_cell(0), Uncovered::Safe);
+            for c in safe.iter().chain(&[0]) {
+                assert!(Board::is_empty(board.board[*c]));
+            }
+            check_mine_count(&board);
+        }
+
+        // the first cell is never a mine, even if it lies outside of the regions
+        let rules = Rules {
+            safe_start: SafeStart::Cell,
+            safe_regions: "0-1".parse().unwrap(),
+            ..Rules::default()
+        };
+        assert!(Board::validate(&[4], 2, &rules).is_err());
+        assert!(Board::validate(&[4], 1, &rules).is_ok());
+        for seed in 0..10 {
+            let mut board = Board::new(&[4], 1, 0, rules.clone(), Some(seed));
+            assert_eq!(board.uncover_cell(3), Uncovered::Safe);
+        }
+        let rules = Rules {
+            safe_start: SafeStart::Off,
+            ..rules
+        };
+        assert!(Board::validate(&[4], 2, &rules).is_ok());
+    }
+
+    #[test]
+    fn test_seed() {
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Moore,
+            ..Rules::default()
+        };
+
+        let mut a = Board::new(&[5, 4, 3, 2], 30, 0, rules.clone(), Some(42));
+        let mut b = Board::new(&[5, 4, 3, 2], 30, 0, rules.clone(), Some(42));
+        let cell = a.index(&[1, 1, 1, 1]).unwrap();
+        a.uncover_cell(cell);
+        b.uncover_cell(cell);
+        assert_eq!(a.board, b.board);
+
+        let mut c = Board::new(&[5, 4, 3, 2], 30, 0, rules, Some(43));
+        c.uncover_cell(cell);
+        assert_ne!(a.board, c.board);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut board = Board::new(&[5, 5], 3, 1, Rules::default(), Some(3));
+        board.uncover_cell(0);
+        let uncovered = board.board.clone();
+        let covered: Vec<_> = (0..board.board.len())
+            .filter(|c| Board::is_covered(board.board[*c]))
+            .collect();
+
+        board.flag_cell(covered[0]);
+        board.mark_cell(covered[1]);
+        // moves that don't change anything are not recorded
+        board.flag_cell(0);
+        assert_eq!(board.moves.len(), 3);
+
+        assert!(board.undo());
+        assert!(board.undo());
+        assert_eq!(board.board, uncovered);
+        assert_eq!((board.mines_flagged, board.mines_marked), (0, 0));
+        assert_eq!(board.undos, 2);
+
+        // uncovered cells can only be undone in practice mode
+        assert!(!board.can_undo());
+        assert!(!board.undo());
+
+        assert!(board.redo());
+        assert_eq!(board.mines_flagged, 1);
+        assert!(board.redo());
+        assert!(!board.redo());
+        assert_eq!(board.mines_marked, 1);
+
+        // a new move clears the undone moves
+        board.undo();
+        board.flag_cell(covered[2]);
+        assert!(!board.redo());
+    }
+
+    #[test]
+    fn test_undo_practice() {
+        let rules = Rules {
+            practice: true,
+            ..Rules::default()
+        };
+        let mut board = Board::new(&[5, 5], 3, 1, rules, Some(3));
+        board.uncover_cell(0);
+        let uncovered = board.board.clone();
+
+        let cell = board
+            .board
+            .iter()
+            .position(|v| Board::is_covered(*v))
+            .unwrap();
+        board.cheat_cell(cell);
+        assert_eq!(board.cheats_remaining, 0);
+
+        assert!(board.undo());
+        assert_eq!(board.board, uncovered);
+        assert_eq!(board.cheats_remaining, 1);
+
+        assert!(board.undo());
+        assert!(board.board.iter().all(|v| !Board::is_uncovered(*v)));
+        assert!(board.mines_placed);
+
+        assert!(board.redo());
+        assert_eq!(board.board, uncovered);
+    }
+
+    #[test]
+    fn test_win_condition() {
+        let board_with = |win_condition| {
+            let rules = Rules {
+                win_condition,
+                ..Rules::default()
+            };
+            let mut board = Board::new(&[4, 4], 3, 0, rules, Some(11));
+            board.uncover_cell(0);
+            board
+        };
+        let cells = |board: &Board, mine: bool| -> Vec<_> {
+            (0..board.board.len())
+                .filter(|c| Board::is_empty(board.board[*c]) != mine)
+                .collect()
+        };
+
+        // uncovering all safe cells flags the remaining mines
+        for win_condition in [WinCondition::Uncover, WinCondition::Any] {
+            let mut board = board_with(win_condition);
+            for cell in cells(&board, false) {
+                board.uncover_cell(cell);
+            }
+            assert!(board.is_won());
+            assert_eq!(board.mines_flagged, 3);
+        }
+
+        let mut board = board_with(WinCondition::Flags);
+        for cell in cells(&board, false) {
+            board.uncover_cell(cell);
+        }
+        assert!(!board.is_won());
+        assert_eq!(board.mines_flagged, 0);
+
+        // flagging all mines only wins if flags are allowed
+        for (win_condition, won) in [
+            (WinCondition::Flags, true),
+            (WinCondition::Uncover, false),
+            (WinCondition::Any, true),
+        ] {
+            let mut board = board_with(win_condition);
+            let mines = cells(&board, true);
+            let won_by_flags = mines.iter().map(|c| board.flag_cell(*c)).last();
+            assert_eq!(won_by_flags, Some(won));
+        }
+    }
+
+    #[test]
+    fn test_chord() {
+        // 1D board: · · 1 * · with the mine at x₁ = 3
+        let mut board = Board::new(
+            &[5],
+            0,
+            0,
+            Rules {
+                win_condition: WinCondition::Flags,
+                ..rules(Neighbourhood::VonNeumann, Boundary::Bounded)
+            },
+            None,
+        );
+        board.place_mines_at(&[3]);
+        assert_eq!(board.uncover_cell(2), Uncovered::Safe);
+
+        // the number isn't satisfied yet
+        assert_eq!(board.chord_cell(2), Uncovered::Safe);
+        assert!(Board::is_covered(board.board[1]));
+
+        board.flag_cell(3);
+        assert_eq!(board.chord_cell(2), Uncovered::Safe);
+        assert!(Board::is_uncovered(board.board[0]));
+        assert!(Board::is_uncovered(board.board[1]));
+        assert!(Board::is_covered(board.board[4]));
+
+        // a wrong flag loses the game
+        let mut board = Board::new(
+            &[5],
+            0,
+            0,
+            rules(Neighbourhood::VonNeumann, Boundary::Bounded),
+            None,
+        );
+        board.place_mines_at(&[3]);
+        board.uncover_cell(2);
+        board.flag_cell(1);
+        assert_eq!(board.chord_cell(2), Uncovered::Lost);
+        assert!(Board::is_covered(board.board[3]));
+    }
+
+    #[test]
+    fn test_bbbv() {
+        let board_with_mines = |size: &[usize], mines: &[usize]| {
+            let mut board = Board::new(
+                size,
+                0,
+                0,
+                rules(Neighbourhood::VonNeumann, Boundary::Bounded),
+                None,
+            );
+            board.place_mines_at(mines);
+            board
+        };
+
+        // two openings: · · 1 * 1 · ·
+        let mut board = board_with_mines(&[7], &[3]);
+        assert_eq!(board.bbbv(), (2, 0));
+        assert_eq!(board.efficiency(), None);
+        board.uncover_cell(0);
+        assert_eq!(board.bbbv(), (2, 1));
+        board.uncover_cell(4);
+        board.uncover_cell(6);
+        assert_eq!(board.bbbv(), (2, 2));
+        assert_eq!(board.move_counts.uncovers, 3);
+        assert_eq!(board.efficiency().map(|e| e.round()), Some(67.0));
+
+        // isolated numbers: 1 * 1 * 1
+        let board = board_with_mines(&[5], &[1, 3]);
+        assert_eq!(board.bbbv(), (3, 0));
+
+        // the solved 3BV follows undo and redo, uncovering the border of an opening doesn't solve it
+        let mut board = board_with_mines(&[7], &[3]);
+        board.rules.practice = true;
+        board.uncover_cell(4);
+        assert_eq!(board.bbbv(), (2, 0));
+        board.uncover_cell(6);
+        assert_eq!(board.bbbv(), (2, 1));
+        board.undo();
+        assert_eq!(board.bbbv(), (2, 0));
+        board.redo();
+        assert_eq!(board.bbbv(), (2, 1));
+    }
+
+    #[test]
+    fn test_validate() {
+        let rules = Rules::default();
+
+        assert_eq!(Board::cells(&[5, 4, 3]), Ok(60));
+        assert!(Board::cells(&[5, 0, 3]).is_err());
+        assert!(Board::cells(&[]).is_err());
+        assert!(Board::cells(&[1, 1, 1, 1, 1, 2, usize::MAX]).is_err());
+        assert!(Board::cells(&[MAX_CELLS + 1]).is_err());
+        assert_eq!(Board::cells(&[2; MAX_DIMENSIONS]), Ok(1024));
+        assert!(Board::cells(&[2; MAX_DIMENSIONS + 1]).is_err());
+
+        // mines are no longer clamped, at least one cell must be free of mines
+        assert!(Board::validate(&[3, 3], 8, &rules).is_ok());
+        assert!(Board::validate(&[3, 3], 9, &rules).is_err());
+
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Chebyshev(4),
+            ..Rules::default()
+        };
+        assert!(Board::validate(&[9; 6], 10, &rules).is_err());
+    }
 }
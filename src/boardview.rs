@@ -1,4 +1,11 @@
-use crate::{board, Options};
+use crate::{
+    board::{self, CellState, Uncovered, MAX_DIMENSIONS},
+    history::HistoryEntry,
+    replay::{Replay, ReplayEvent},
+    savegame::SaveGame,
+    settings::axis_name,
+    solver, Options,
+};
 use cursive::{
     event::{Event, EventResult},
     theme::{ColorStyle, Style},
@@ -7,18 +14,50 @@ use cursive::{
 };
 use std::{
     cmp::max,
-    fs::{create_dir, OpenOptions},
-    io::Write,
-    path::Path,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 mod tileset;
 
+/// The keys moving the cursor backwards and forwards along x₁, x₂, …
+pub const AXIS_KEYS: [(char, char); MAX_DIMENSIONS] = [
+    ('a', 'd'),
+    ('w', 's'),
+    ('q', 'e'),
+    ('j', 'l'),
+    ('i', 'k'),
+    ('u', 'o'),
+    ('t', 'g'),
+    ('v', 'b'),
+    ('n', 'm'),
+    (',', '.'),
+];
+
+/// The keys of actions that are recorded in replays, in addition to `AXIS_KEYS`.
+const ACTION_KEYS: &str = "hpfrcxzy ";
+
+/// Returns the axis a key moves the cursor along and the direction.
+fn axis_of_key(key: char) -> Option<(usize, i8)> {
+    AXIS_KEYS
+        .iter()
+        .enumerate()
+        .find_map(|(axis, (back, forth))| {
+            if key == *back {
+                Some((axis, -1))
+            } else if key == *forth {
+                Some((axis, 1))
+            } else {
+                None
+            }
+        })
+}
+
 /// This struct is responsible for the interaction between the user and the board.
 pub struct BoardView {
     board: board::Board,
-    current_view: (usize, usize, usize, usize),
-    current_pos: (usize, usize),
+    /// Coordinates of the cursor as (x₁, x₂, …, xₙ)
+    cursor: Vec<usize>,
     tileset: tileset::Tileset,
     /// Spacing factor for the individual cells
     h_space: usize,
@@ -30,20 +69,65 @@ pub struct BoardView {
     label_len: usize,
     /// Path of the history file
     options: Options,
+    /// The hint shown after pressing h
+    hint: Option<solver::Deduction>,
+    /// Explanation of the hint
+    hint_message: String,
+    /// The mine probabilities shown by the overlay, None if the overlay is hidden
+    probabilities: Option<solver::Probabilities>,
+    /// The game is saved to this file after each move
+    save_path: Option<PathBuf>,
+    /// Time spent playing before `self.started`, e.g. before the game was saved
+    elapsed: Duration,
+    /// Time at which the game was started or resumed
+    started: Instant,
+    /// False while the clock is paused, e.g. while a dialog is shown
+    running: bool,
+    /// The result of the game as written to the history file, None while the game is running
+    result: Option<HistoryEntry>,
+    /// The keys pressed so far, written to a replay file when the game ends
+    replay_events: Vec<ReplayEvent>,
 }
 
 impl BoardView {
     pub fn new(
-        size: (usize, usize, usize, usize, usize, usize),
+        size: &[usize],
         mines: u32,
         cheats: u32,
+        rules: board::Rules,
+        seed: Option<u64>,
         options: Options,
     ) -> Self {
-        let board = board::Board::new(size, mines, cheats);
+        let board = board::Board::new(size, mines, cheats, rules, seed);
+        let save_path = options.save_path.clone();
+
+        Self::with_board(board, save_path, options)
+    }
+
+    /// Resumes a saved game, which will be saved to save_path after each move.
+    pub fn from_save(
+        save: &SaveGame,
+        save_path: PathBuf,
+        options: Options,
+    ) -> Result<Self, String> {
+        let board = save.to_board()?;
 
-        let current_view = (0, 0, 0, 0);
-        let current_pos = (0, 0);
+        let mut view = Self::with_board(board, Some(save_path), options);
+        view.cursor = save.cursor.clone();
+        view.elapsed = Duration::from_secs_f64(save.elapsed.max(0.0));
+        view.replay_events = save.replay_events.clone();
 
+        Ok(view)
+    }
+
+    /// Creates the board of a replay as it was before the first move.
+    pub fn from_replay(replay: &Replay, options: Options) -> Result<Self, String> {
+        let board = replay.to_board()?;
+
+        Ok(Self::with_board(board, None, options))
+    }
+
+    fn with_board(board: board::Board, save_path: Option<PathBuf>, options: Options) -> Self {
         // get tileset options from config
         let use_color = if let Some(ref config) = options.config {
             config.use_color
@@ -66,81 +150,260 @@ impl BoardView {
         };
 
         Self {
+            cursor: vec![0; board.size().len()],
             board,
-            current_view,
-            current_pos,
             tileset,
             h_space,
             view_padding: 2,
-            y_offset: 5,
+            y_offset: 9,
             label_len: 6,
             options,
+            hint: None,
+            hint_message: String::new(),
+            probabilities: None,
+            save_path,
+            elapsed: Duration::ZERO,
+            started: Instant::now(),
+            running: true,
+            result: None,
+            replay_events: Vec::new(),
+        }
+    }
+
+    /// Returns the time spent playing.
+    pub fn elapsed(&self) -> Duration {
+        if self.running {
+            self.elapsed + self.started.elapsed()
+        } else {
+            self.elapsed
+        }
+    }
+
+    /// Stops the clock, e.g. while a dialog is shown.
+    pub fn pause(&mut self) {
+        if self.running {
+            self.elapsed += self.started.elapsed();
+            self.running = false;
+        }
+    }
+
+    /// Restarts the clock, unless the game has ended.
+    pub fn resume(&mut self) {
+        if !self.running && self.result.is_none() {
+            self.started = Instant::now();
+            self.running = true;
+        }
+    }
+
+    /// Returns the result of the game once it has ended.
+    pub fn result(&self) -> Option<HistoryEntry> {
+        self.result.clone()
+    }
+
+    /// Saves the game to `self.save_path`, unless it has already ended.
+    pub fn save(&self) {
+        if self.result.is_some() {
+            return;
+        }
+
+        if let Some(save_path) = &self.save_path {
+            let save = SaveGame::new(
+                &self.board,
+                &self.cursor,
+                self.elapsed().as_secs_f64(),
+                self.replay_events.clone(),
+            );
+            let _ = save.write(save_path);
         }
     }
 
     /// Returns the current position as a formatted String.
     pub fn format_pos_string(&self) -> String {
+        format!("Position {}", Self::format_coordinates(&self.cursor))
+    }
+
+    /// Returns coordinates as a formatted String, e.g. "(1, 2, 0)".
+    pub fn format_coordinates(coordinates: &[usize]) -> String {
+        let coordinates: Vec<String> = coordinates.iter().map(|x| x.to_string()).collect();
+        format!("({})", coordinates.join(", "))
+    }
+
+    /// Returns a cell as a formatted String, ordered (x₁, x₂, …, xₙ).
+    pub fn format_cell_string(&self, cell: usize) -> String {
+        Self::format_coordinates(&self.board.coordinates(cell))
+    }
+
+    /// Returns the cell under the cursor.
+    fn current_cell(&self) -> usize {
+        self.board
+            .index(&self.cursor)
+            .expect("the cursor is on the board")
+    }
+
+    /// Looks for a cell that can be deduced from the uncovered cells and explains the deduction.
+    pub fn show_hint(&mut self) {
+        self.hint = solver::hint(&self.board.observe());
+
+        self.hint_message = match &self.hint {
+            Some(hint) if hint.reason.is_empty() => format!(
+                "{} is {}, implied by the number of mines left",
+                self.format_cell_string(hint.cell),
+                if hint.mine { "a mine" } else { "safe" },
+            ),
+            Some(hint) => {
+                let mut reason: Vec<String> = hint
+                    .reason
+                    .iter()
+                    .take(4)
+                    .map(|c| self.format_cell_string(*c))
+                    .collect();
+                if hint.reason.len() > 4 {
+                    reason.push(format!("… ({} cells)", hint.reason.len()));
+                }
+
+                format!(
+                    "{} is {}, implied by {}",
+                    self.format_cell_string(hint.cell),
+                    if hint.mine { "a mine" } else { "safe" },
+                    reason.join(", ")
+                )
+            }
+            None => "no cell can be deduced without guessing".to_string(),
+        };
+    }
+
+    /// Shows or hides the mine probability overlay.
+    pub fn toggle_probabilities(&mut self) {
+        self.probabilities = match self.probabilities {
+            Some(_) => None,
+            None => Some(solver::probabilities(&self.board.observe())),
+        };
+    }
+
+    /// Recomputes the mine probabilities if the overlay is shown.
+    fn update_probabilities(&mut self) {
+        if self.probabilities.is_some() {
+            self.probabilities = Some(solver::probabilities(&self.board.observe()));
+        }
+    }
+
+    /// Removes the current hint.
+    fn clear_hint(&mut self) {
+        self.hint = None;
+        self.hint_message.clear();
+    }
+
+    /// Returns the time, the number of moves, the 3BV and the efficiency as a formatted String.
+    pub fn format_stats_string(&self) -> String {
+        let (bbbv, bbbv_solved) = self.board.bbbv();
+        let efficiency = match self.board.efficiency() {
+            Some(e) => format!("{:.0} %", e),
+            None => "-".to_string(),
+        };
+
         format!(
-            "Position ({}, {}, {}, {}, {}, {})",
-            self.current_pos.1,
-            self.current_pos.0,
-            self.current_view.3,
-            self.current_view.2,
-            self.current_view.1,
-            self.current_view.0
+            "Stats    {} s, {} moves, 3BV {}/{}, efficiency {}",
+            self.elapsed().as_secs(),
+            self.board.move_counts.total(),
+            bbbv_solved,
+            bbbv,
+            efficiency
         )
     }
 
     /// Returns the board size as a formatted String.
     pub fn format_size_string(&self) -> String {
-        format!(
-            "Size     ({}, {}, {}, {}, {}, {})",
-            self.board.board.dim().5,
-            self.board.board.dim().4,
-            self.board.board.dim().3,
-            self.board.board.dim().2,
-            self.board.board.dim().1,
-            self.board.board.dim().0,
-        )
+        format!("Size     {}", Self::format_coordinates(self.board.size()))
+    }
+
+    /// Moves the cursor by d along the given axis, 0 being x₁,
+    /// wrapping around periodic axes and bounded by the board size otherwise.
+    pub fn change_pos(&mut self, axis: usize, d: i8) {
+        if axis < self.cursor.len() {
+            self.cursor[axis] = self.move_along(axis, self.cursor[axis], d);
+        }
+    }
+
+    /// Moves the coordinate x along the given axis by d.
+    fn move_along(&self, axis: usize, x: usize, d: i8) -> usize {
+        let size = self.board.size()[axis];
+
+        match self.board.rules.topology.axis(axis) {
+            board::Boundary::Periodic => board::Boundary::Periodic
+                .wrap(x as isize + d as isize, size)
+                .unwrap_or(x),
+            _ => Self::add_checked(x, d, 0, size - 1),
+        }
     }
 
-    /// Changes `self.current_view` and `self.current_pos` according to direction, bounded by the board size.
-    pub fn change_pos(&mut self, direction: (i8, i8, i8, i8, i8, i8)) {
-        let (s6, s5, s4, s3, s2, s1) = self.board.board.dim();
-        let (d6, d5, d4, d3, d2, d1) = direction;
-
-        self.current_view.0 = Self::add_checked(self.current_view.0, d6, 0, s6 - 1);
-        self.current_view.1 = Self::add_checked(self.current_view.1, d5, 0, s5 - 1);
-        self.current_view.2 = Self::add_checked(self.current_view.2, d4, 0, s4 - 1);
-        self.current_view.3 = Self::add_checked(self.current_view.3, d3, 0, s3 - 1);
-        self.current_pos.0 = Self::add_checked(self.current_pos.0, d2, 0, s2 - 1);
-        self.current_pos.1 = Self::add_checked(self.current_pos.1, d1, 0, s1 - 1);
+    /// Returns the coordinate of the slice at offset d from x along the given axis,
+    /// or None if there is no such slice.
+    fn slice_along(&self, axis: usize, x: usize, d: i8) -> Option<usize> {
+        let size = self.board.size()[axis];
+        let slice = self
+            .board
+            .rules
+            .topology
+            .axis(axis)
+            .wrap(x as isize + d as isize, size)?;
+
+        if slice == x && d != 0 {
+            None
+        } else {
+            Some(slice)
+        }
     }
 
-    fn draw_board(&self, printer: &Printer, offset: (usize, usize), view: (i8, i8, i8, i8)) {
-        let (s6, s5, s4, s3, y, x) = self.board.board.dim();
+    /// Returns the size of the slices that are drawn as (x₁, x₂), a 1D board is drawn as a single row.
+    fn slice_size(&self) -> (usize, usize) {
+        let size = self.board.size();
+        (size[0], size.get(1).copied().unwrap_or(1))
+    }
 
-        let x6 = Self::add_checked(self.current_view.0, view.0, 0, s6 - 1);
-        let x5 = Self::add_checked(self.current_view.1, view.1, 0, s5 - 1);
-        let x4 = Self::add_checked(self.current_view.2, view.2, 0, s4 - 1);
-        let x3 = Self::add_checked(self.current_view.3, view.3, 0, s3 - 1);
+    /// Draws the slice through the cursor spanned by x₁ and x₂, shifted by d along the given axis if there is one.
+    fn draw_board(&self, printer: &Printer, offset: (usize, usize), shift: Option<(usize, i8)>) {
+        let (width, height) = self.slice_size();
 
         // valid view ?
-        if (x6 == self.current_view.0 && view.0 != 0)
-            || (x5 == self.current_view.1 && view.1 != 0)
-            || (x4 == self.current_view.2 && view.2 != 0)
-            || (x3 == self.current_view.3 && view.3 != 0)
-        {
-            return;
+        let mut coordinates = self.cursor.clone();
+        if let Some((axis, d)) = shift {
+            match self.slice_along(axis, coordinates[axis], d) {
+                Some(x) => coordinates[axis] = x,
+                None => return,
+            }
         }
 
-        for x2 in 0..y {
-            for x1 in 0..x {
-                let value = self.board.board[[x6, x5, x4, x3, x2, x1]];
-                let (string, mut style) = self.tileset.format_cell(value);
+        let observation = self.board.observe();
+        for x2 in 0..height {
+            for x1 in 0..width {
+                coordinates[0] = x1;
+                if let Some(y) = coordinates.get_mut(1) {
+                    *y = x2;
+                }
+                let cell = self
+                    .board
+                    .index(&coordinates)
+                    .expect("the slice is on the board");
+
+                let state = observation.cell(cell);
+                let (string, mut style) = match (&self.probabilities, state) {
+                    (Some(p), CellState::Covered | CellState::Flagged | CellState::Marked) => {
+                        self.tileset.format_probability(state, p.get(cell))
+                    }
+                    _ => self.tileset.format_cell(state),
+                };
+
+                // highlight the cells of the hint
+                if let Some(hint) = &self.hint {
+                    if hint.cell == cell {
+                        style = self.tileset.format_hint_colorstyle(Some(hint.mine));
+                    } else if hint.reason.contains(&cell) {
+                        style = self.tileset.format_hint_colorstyle(None);
+                    }
+                }
 
                 // highlight if cursor is at current cell
-                if (x2, x1) == self.current_pos {
+                if cell == self.current_cell() {
                     style = ColorStyle::merge(style, ColorStyle::highlight());
                 }
 
@@ -153,6 +416,13 @@ impl BoardView {
         }
     }
 
+    /// Returns the axes beyond x₂ that have additional views, i.e. those of size greater than 1.
+    fn view_axes(&self) -> Vec<usize> {
+        (2..self.board.size().len())
+            .filter(|axis| self.board.size()[*axis] > 1)
+            .collect()
+    }
+
     // a + b if: min <= (a + b) <= max
     pub const fn add_checked(a: usize, b: i8, min: usize, max: usize) -> usize {
         let mut a = a as isize;
@@ -167,61 +437,62 @@ impl BoardView {
         }
     }
 
-    /// Writes the result and options of the current game to the history file.
-    fn store_result(&self, result: &str) {
-        if let Some(history_path) = &self.options.history_path {
-            // attempt to create parent directory if it doesn't exist
-            if let Some(parent) = history_path.parent() {
-                if !Path::exists(parent) {
-                    let _ = create_dir(parent);
-                }
-            }
+    /// Writes the result and options of the current game to the history file and the replay directory,
+    /// and removes the saved game.
+    fn store_result(&mut self, result: &str) {
+        self.pause();
+        if let Some(save_path) = &self.save_path {
+            SaveGame::remove(save_path);
+        }
 
-            // write to history file
-            if let Ok(mut file) = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(history_path)
-            {
-                let size = self.board.board.dim();
-                let _ = file.write_all(
-                    format!("{{\"result\": \"{}\", \"mines\": {}, \"cheats\": {}, \"size\": [{}, {}, {}, {}, {}, {}]}}\n",
-                        result,
-                        self.board.mines_total,
-                        self.board.cheats_total,
-                        size.5,
-                        size.4,
-                        size.3,
-                        size.2,
-                        size.1,
-                        size.0
-                    ).as_bytes()
-                );
-            }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        if let Some(replay_dir) = &self.options.replay_dir {
+            let replay = Replay::new(&self.board, result, self.replay_events.clone());
+            let _ = replay.write_new(replay_dir, &timestamp.to_string());
+        }
+
+        let entry = HistoryEntry::new(
+            &self.board,
+            result,
+            self.elapsed().as_secs_f64(),
+            self.board.bbbv(),
+            timestamp,
+        );
+        if let Some(history_path) = &self.options.history_path {
+            let _ = entry.append(history_path);
         }
+        self.result = Some(entry);
     }
 }
 
 impl cursive::view::View for BoardView {
     fn draw(&self, printer: &Printer) {
         // the size of a single view
-        let (x6, x5, x4, x3, y, x) = self.board.board.dim();
-        let view_width = max(x * self.h_space, self.label_len);
-        let view_height = y;
+        let (width, view_height) = self.slice_size();
+        let view_width = max(width * self.h_space, self.label_len);
 
         // print status (position, size, …)
-        let current_cell = self.board.board[[
-            self.current_view.0,
-            self.current_view.1,
-            self.current_view.2,
-            self.current_view.3,
-            self.current_pos.0,
-            self.current_pos.1,
-        ]];
-        let neighboring_mines = if board::Board::is_uncovered(current_cell) {
-            format!("{}", board::Board::mines(current_cell))
-        } else {
-            "?".to_string()
+        let observation = self.board.observe();
+        let current_cell = observation.cell(self.current_cell());
+        let neighboring_mines = match current_cell {
+            CellState::Uncovered(mines) => mines.to_string(),
+            _ => "?".to_string(),
+        };
+        let probability = match &self.probabilities {
+            Some(p) if !matches!(current_cell, CellState::Uncovered(_)) => {
+                match p.get(self.current_cell()) {
+                    Some(p_mine) => format!(
+                        ", mine: {}{:.1} %",
+                        if p.exact { "" } else { "~" },
+                        p_mine * 100.0
+                    ),
+                    None => ", mine: unknown".to_string(),
+                }
+            }
+            _ => "".to_string(),
         };
 
         printer.print((0, 0), self.format_pos_string().as_str());
@@ -229,80 +500,60 @@ impl cursive::view::View for BoardView {
         printer.print(
             (0, 2),
             format!(
-                "Mines    {}+{}/{} ({})",
-                self.board.mines_flagged,
-                self.board.mines_marked,
-                self.board.mines_total,
-                neighboring_mines
+                "Mines    {}+{}/{} ({}{})",
+                observation.mines_flagged(),
+                observation.mines_marked(),
+                observation.mines_total(),
+                neighboring_mines,
+                probability
             )
             .as_str(),
         );
         printer.print(
             (0, 3),
-            format!("Cheats   {}", self.board.cheats_remaining).as_str(),
+            format!("Cheats   {}", observation.cheats_remaining()).as_str(),
         );
+        let fallback = if self.board.solvable == Some(false) {
+            " (guessing required)"
+        } else {
+            ""
+        };
+        printer.print(
+            (0, 4),
+            format!("Rules    {}{}", self.board.rules, fallback).as_str(),
+        );
+        printer.print((0, 5), format!("Seed     {}", self.board.seed()).as_str());
+        printer.print((0, 6), self.format_stats_string().as_str());
+        if !self.hint_message.is_empty() {
+            printer.print((0, 7), format!("Hint     {}", self.hint_message).as_str());
+        }
 
         // print current view of the board
-        self.draw_board(printer, (0, self.y_offset), (0, 0, 0, 0));
+        self.draw_board(printer, (0, self.y_offset), None);
 
-        // print additional views
+        // print additional views, one column of slices at -1 and +1 per axis
         let space = self.view_padding;
         let y = view_height + self.y_offset + space;
         let mut x = 0;
 
-        // x3
-        if x3 > 1 {
-            printer.print((x, y - 1), "x₃: -1");
-            self.draw_board(printer, (x, y), (0, 0, 0, -1));
-            printer.print((x, y + view_height + space - 1), "x₃: +1");
-            self.draw_board(printer, (x, y + view_height + space), (0, 0, 0, 1));
+        for axis in self.view_axes() {
+            let name = axis_name(axis);
+            printer.print((x, y - 1), format!("{}: -1", name).as_str());
+            self.draw_board(printer, (x, y), Some((axis, -1)));
+            printer.print(
+                (x, y + view_height + space - 1),
+                format!("{}: +1", name).as_str(),
+            );
+            self.draw_board(printer, (x, y + view_height + space), Some((axis, 1)));
             x += view_width + space;
         }
-
-        // x4
-        if x4 > 1 {
-            printer.print((x, y - 1), "x₄: -1");
-            self.draw_board(printer, (x, y), (0, 0, -1, 0));
-            printer.print((x, y + view_height + space - 1), "x₄: +1");
-            self.draw_board(printer, (x, y + view_height + space), (0, 0, 1, 0));
-            x += view_width + space;
-        }
-
-        // x5
-        if x5 > 1 {
-            printer.print((x, y - 1), "x₅: -1");
-            self.draw_board(printer, (x, y), (0, -1, 0, 0));
-            printer.print((x, y + view_height + space - 1), "x₅: +1");
-            self.draw_board(printer, (x, y + view_height + space), (0, 1, 0, 0));
-            x += view_width + space;
-        }
-
-        // x6
-        if x6 > 1 {
-            printer.print((x, y - 1), "x₆: -1");
-            self.draw_board(printer, (x, y), (-1, 0, 0, 0));
-            printer.print((x, y + view_height + space - 1), "x₆: +1");
-            self.draw_board(printer, (x, y + view_height + space), (1, 0, 0, 0));
-        }
     }
 
     fn required_size(&mut self, _: Vec2) -> Vec2 {
-        let (x6, x5, x4, x3, y, x) = self.board.board.dim();
+        let (x, y) = self.slice_size();
 
         // number of additional views
-        let mut num_views_x = 0;
-        if x6 > 1 {
-            num_views_x += 1;
-        }
-        if x5 > 1 {
-            num_views_x += 1;
-        }
-        if x4 > 1 {
-            num_views_x += 1;
-        }
-        if x3 > 1 {
-            num_views_x += 1;
-        }
+        let num_views_x = self.view_axes().len();
 
         // number of spaces between the views in x direction
         let num_padding_x = if num_views_x != 0 { num_views_x - 1 } else { 0 };
@@ -323,38 +574,80 @@ impl cursive::view::View for BoardView {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        match event {
+        // record the event for the replay
+        if let Event::Char(key) = event {
+            if self.result.is_none() && (axis_of_key(key).is_some() || ACTION_KEYS.contains(key)) {
+                self.replay_events.push(ReplayEvent {
+                    time: self.elapsed().as_secs_f64(),
+                    key,
+                });
+            }
+
             // cursor movement
-            Event::Char('w') => self.change_pos((0, 0, 0, 0, -1, 0)),
-            Event::Char('s') => self.change_pos((0, 0, 0, 0, 1, 0)),
-            Event::Char('a') => self.change_pos((0, 0, 0, 0, 0, -1)),
-            Event::Char('d') => self.change_pos((0, 0, 0, 0, 0, 1)),
-            Event::Char('q') => self.change_pos((0, 0, 0, -1, 0, 0)),
-            Event::Char('e') => self.change_pos((0, 0, 0, 1, 0, 0)),
-            Event::Char('i') => self.change_pos((0, -1, 0, 0, 0, 0)),
-            Event::Char('k') => self.change_pos((0, 1, 0, 0, 0, 0)),
-            Event::Char('j') => self.change_pos((0, 0, -1, 0, 0, 0)),
-            Event::Char('l') => self.change_pos((0, 0, 1, 0, 0, 0)),
-            Event::Char('u') => self.change_pos((-1, 0, 0, 0, 0, 0)),
-            Event::Char('o') => self.change_pos((1, 0, 0, 0, 0, 0)),
+            if let Some((axis, d)) = axis_of_key(key) {
+                self.change_pos(axis, d);
+                return EventResult::Consumed(None);
+            }
+        }
+
+        match event {
+            // show a hint
+            Event::Char('h') => self.show_hint(),
+
+            // show or hide the mine probabilities
+            Event::Char('p') => self.toggle_probabilities(),
 
             // uncover cell
             Event::Char(' ') => {
-                let (x2, x1) = self.current_pos;
-                let (x6, x5, x4, x3) = self.current_view;
+                self.clear_hint();
+                let cell = self.current_cell();
+
+                match self.board.uncover_cell(cell) {
+                    Uncovered::Lost => {
+                        self.store_result("lost");
+                        return EventResult::Ignored;
+                    }
+                    Uncovered::Undone => {
+                        self.hint_message = "That was a mine, the move has been undone".to_string();
+                    }
+                    Uncovered::Safe if self.board.is_won() => {
+                        self.store_result("won");
+                        return EventResult::Ignored;
+                    }
+                    Uncovered::Safe => {}
+                }
+                self.update_probabilities();
+            }
 
-                if self.board.uncover_cell((x6, x5, x4, x3, x2, x1)) {
-                    self.store_result("lost");
-                    return EventResult::Ignored;
+            // uncover the neighbours of a cell whose mines have been flagged
+            Event::Char('x') => {
+                self.clear_hint();
+                let cell = self.current_cell();
+
+                match self.board.chord_cell(cell) {
+                    Uncovered::Lost => {
+                        self.store_result("lost");
+                        return EventResult::Ignored;
+                    }
+                    Uncovered::Undone => {
+                        self.hint_message =
+                            "A flag was wrong, the move has been undone".to_string();
+                    }
+                    Uncovered::Safe if self.board.is_won() => {
+                        self.store_result("won");
+                        return EventResult::Ignored;
+                    }
+                    Uncovered::Safe => {}
                 }
+                self.update_probabilities();
             }
 
             // flag cell
             Event::Char('f') => {
-                let (x2, x1) = self.current_pos;
-                let (x6, x5, x4, x3) = self.current_view;
+                self.clear_hint();
+                let cell = self.current_cell();
 
-                if self.board.flag_cell((x6, x5, x4, x3, x2, x1)) {
+                if self.board.flag_cell(cell) {
                     self.store_result("won");
                     return EventResult::Ignored;
                 }
@@ -362,26 +655,64 @@ impl cursive::view::View for BoardView {
 
             // mark cell
             Event::Char('r') => {
-                let (x2, x1) = self.current_pos;
-                let (x6, x5, x4, x3) = self.current_view;
+                self.clear_hint();
+                let cell = self.current_cell();
 
-                self.board.mark_cell((x6, x5, x4, x3, x2, x1));
+                self.board.mark_cell(cell);
             }
 
             // cheat
             Event::Char('c') => {
-                let (x2, x1) = self.current_pos;
-                let (x6, x5, x4, x3) = self.current_view;
+                self.clear_hint();
+                let cell = self.current_cell();
 
-                if self.board.cheat_cell((x6, x5, x4, x3, x2, x1)) {
+                if self.board.cheat_cell(cell) {
                     self.store_result("won");
                     return EventResult::Ignored;
                 }
+                self.update_probabilities();
+            }
+
+            // undo the last move
+            Event::Char('z') => {
+                self.clear_hint();
+
+                if self.board.undo() {
+                    if self.board.is_won() {
+                        self.store_result("won");
+                        return EventResult::Ignored;
+                    }
+                    self.update_probabilities();
+                } else if self.board.moves().is_empty() {
+                    self.hint_message = "There is no move to undo".to_string();
+                } else {
+                    self.hint_message =
+                        "Only flags and marks can be undone outside of practice mode".to_string();
+                }
+            }
+
+            // redo the last undone move
+            Event::Char('y') => {
+                self.clear_hint();
+
+                if self.board.redo() {
+                    if self.board.is_won() {
+                        self.store_result("won");
+                        return EventResult::Ignored;
+                    }
+                    self.update_probabilities();
+                } else {
+                    self.hint_message = "There is no move to redo".to_string();
+                }
             }
 
             _ => return EventResult::Ignored,
         }
 
+        if let Event::Char(' ' | 'x' | 'f' | 'r' | 'c' | 'z' | 'y') = event {
+            self.save();
+        }
+
         EventResult::Consumed(None)
     }
 }
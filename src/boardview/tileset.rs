@@ -1,4 +1,4 @@
-use super::board;
+use super::board::CellState;
 use cursive::theme::{BaseColor, Color, ColorStyle};
 
 /// Used to convert the value of a cell to a styled string.
@@ -17,15 +17,15 @@ impl Tileset {
     }
 
     /// Checks `self.use_color` and `self.use_unicode` and returns a formatted `String` and `Colorstyle`.
-    pub fn format_cell(&self, value: u16) -> (String, ColorStyle) {
+    pub fn format_cell(&self, state: CellState) -> (String, ColorStyle) {
         let string = if self.use_unicode {
-            Self::format_cell_unicode(value)
+            Self::format_cell_unicode(state)
         } else {
-            Self::format_cell_ascii(value)
+            Self::format_cell_ascii(state)
         };
 
         let style = if self.use_color {
-            Self::format_cell_colorstyle(value)
+            Self::format_cell_colorstyle(state)
         } else {
             ColorStyle::inherit_parent()
         };
@@ -33,66 +33,86 @@ impl Tileset {
         (string, style)
     }
 
-    /// Formats value using ASCII characters.
-    pub fn format_cell_ascii(value: u16) -> String {
-        if board::Board::is_covered(value) {
-            "#".to_string()
-        } else if board::Board::is_flagged(value) {
-            "X".to_string()
-        } else if board::Board::is_marked(value) {
-            "?".to_string()
-        } else if board::Board::is_empty(value) {
-            let value = board::Board::mines(value);
-            match value {
-                0 => ".".to_string(),
-                1..=15 => format!("{:X}", value),
-                _ => "+".to_string(),
-            }
+    /// Formats a covered cell, shaded according to the probability p of it containing a mine.
+    /// Without colors the first digit of the percentage is shown instead, or "?" if the probability is unknown.
+    pub fn format_probability(&self, state: CellState, p: Option<f64>) -> (String, ColorStyle) {
+        let p = match p {
+            Some(p) => p,
+            None if self.use_color => return self.format_cell(state),
+            None => return ("?".to_string(), ColorStyle::inherit_parent()),
+        };
+
+        if self.use_color {
+            let (string, _) = self.format_cell(state);
+            let red = (p.clamp(0.0, 1.0) * 5.0).round() as u8;
+            let style = ColorStyle::new(
+                Color::Dark(BaseColor::Black),
+                Color::RgbLowRes(red, 5 - red, 0),
+            );
+            (string, style)
         } else {
-            "*".to_string()
+            let digit = ((p * 10.0) as u8).min(9);
+            (digit.to_string(), ColorStyle::inherit_parent())
         }
     }
 
-    /// Formats value using Unicode characters.
-    pub fn format_cell_unicode(value: u16) -> String {
-        if board::Board::is_covered(value) {
-            "▮".to_string()
-        } else if board::Board::is_flagged(value) {
-            "⚑".to_string()
-        } else if board::Board::is_marked(value) {
-            "?".to_string()
-        } else if board::Board::is_empty(value) {
-            let value = board::Board::mines(value);
-            match value {
-                0 => "·".to_string(),
-                1..=15 => format!("{:X}", value),
-                _ => "+".to_string(),
-            }
-        } else {
-            "*".to_string()
+    /// Returns the `ColorStyle` used to highlight the cells of a hint,
+    /// mine is None for the uncovered cells that imply the hint.
+    pub fn format_hint_colorstyle(&self, mine: Option<bool>) -> ColorStyle {
+        if !self.use_color {
+            return ColorStyle::highlight_inactive();
+        }
+
+        let fg = Color::Dark(BaseColor::Black);
+        match mine {
+            Some(false) => ColorStyle::new(fg, Color::Light(BaseColor::Green)),
+            Some(true) => ColorStyle::new(fg, Color::Light(BaseColor::Red)),
+            None => ColorStyle::new(fg, Color::Light(BaseColor::Yellow)),
         }
     }
 
-    /// Returns the  `ColorStyle` that matches value.
-    pub fn format_cell_colorstyle(value: u16) -> ColorStyle {
+    /// Formats the state of a cell using ASCII characters.
+    pub fn format_cell_ascii(state: CellState) -> String {
+        match state {
+            CellState::Covered => "#".to_string(),
+            CellState::Flagged => "X".to_string(),
+            CellState::Marked => "?".to_string(),
+            CellState::Uncovered(0) => ".".to_string(),
+            CellState::Uncovered(mines @ 1..=15) => format!("{:X}", mines),
+            CellState::Uncovered(_) => "+".to_string(),
+            CellState::Exploded => "*".to_string(),
+        }
+    }
+
+    /// Formats the state of a cell using Unicode characters.
+    pub fn format_cell_unicode(state: CellState) -> String {
+        match state {
+            CellState::Covered => "▮".to_string(),
+            CellState::Flagged => "⚑".to_string(),
+            CellState::Marked => "?".to_string(),
+            CellState::Uncovered(0) => "·".to_string(),
+            CellState::Uncovered(mines @ 1..=15) => format!("{:X}", mines),
+            CellState::Uncovered(_) => "+".to_string(),
+            CellState::Exploded => "✹".to_string(),
+        }
+    }
+
+    /// Returns the  `ColorStyle` that matches the state of a cell.
+    pub fn format_cell_colorstyle(state: CellState) -> ColorStyle {
         let bg = Color::Dark(BaseColor::White);
 
-        if board::Board::is_covered(value) {
-            ColorStyle::new(Color::Dark(BaseColor::Black), bg)
-        } else if board::Board::is_flagged(value) {
-            ColorStyle::new(Color::Dark(BaseColor::Blue), bg)
-        } else if board::Board::is_marked(value) {
-            ColorStyle::new(Color::Dark(BaseColor::Magenta), bg)
-        } else if board::Board::is_empty(value) {
-            match board::Board::mines(value) {
-                0 => ColorStyle::new(Color::Dark(BaseColor::Cyan), bg),
-                1 | 2 | 3 => ColorStyle::new(Color::RgbLowRes(0, 3, 0), bg),
-                4 | 5 | 6 => ColorStyle::new(Color::RgbLowRes(1, 2, 0), bg),
-                7 | 8 | 9 => ColorStyle::new(Color::RgbLowRes(2, 1, 0), bg),
-                _ => ColorStyle::new(Color::RgbLowRes(3, 0, 0), bg),
+        match state {
+            CellState::Covered => ColorStyle::new(Color::Dark(BaseColor::Black), bg),
+            CellState::Flagged => ColorStyle::new(Color::Dark(BaseColor::Blue), bg),
+            CellState::Marked => ColorStyle::new(Color::Dark(BaseColor::Magenta), bg),
+            CellState::Uncovered(0) => ColorStyle::new(Color::Dark(BaseColor::Cyan), bg),
+            CellState::Uncovered(1..=3) => ColorStyle::new(Color::RgbLowRes(0, 3, 0), bg),
+            CellState::Uncovered(4..=6) => ColorStyle::new(Color::RgbLowRes(1, 2, 0), bg),
+            CellState::Uncovered(7..=9) => ColorStyle::new(Color::RgbLowRes(2, 1, 0), bg),
+            CellState::Uncovered(_) => ColorStyle::new(Color::RgbLowRes(3, 0, 0), bg),
+            CellState::Exploded => {
+                ColorStyle::new(Color::Dark(BaseColor::Black), Color::Light(BaseColor::Red))
             }
-        } else {
-            ColorStyle::inherit_parent()
         }
     }
 }
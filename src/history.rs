@@ -0,0 +1,362 @@
+use crate::board::Board;
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    fmt,
+    fs::{create_dir_all, read_to_string, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+#[cfg(test)]
+#[path = "history_tests.rs"]
+mod history_tests;
+
+/// A finished game as stored in the history file, fields missing in entries written by older versions
+/// are filled with the values those versions used.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// "won" or "lost"
+    pub result: String,
+    pub mines: u32,
+    pub cheats: u32,
+    /// Size of the board as (x₁, x₂, …, xₙ), older versions always stored six axes
+    pub size: Vec<usize>,
+    #[serde(default = "default_neighbourhood")]
+    pub neighbourhood: String,
+    #[serde(default = "default_topology")]
+    pub topology: String,
+    #[serde(default = "default_safe_start")]
+    pub safe_start: String,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default = "default_generator")]
+    pub generator: String,
+    #[serde(default = "default_win_condition")]
+    pub win_condition: String,
+    #[serde(default)]
+    pub practice: bool,
+    #[serde(default = "default_safe_regions")]
+    pub safe_regions: String,
+    #[serde(default)]
+    pub undos: u32,
+    /// Time spent playing in seconds
+    #[serde(default)]
+    pub time: Option<f64>,
+    #[serde(default)]
+    pub uncovers: u32,
+    #[serde(default)]
+    pub chords: u32,
+    #[serde(default)]
+    pub flags: u32,
+    #[serde(default)]
+    pub marks: u32,
+    #[serde(default)]
+    pub cheats_used: Option<u32>,
+    #[serde(default, rename = "3bv")]
+    pub bbbv: Option<u32>,
+    #[serde(default, rename = "3bv_solved")]
+    pub bbbv_solved: Option<u32>,
+    /// Seconds since the Unix epoch at which the game ended
+    #[serde(default)]
+    pub timestamp: Option<u64>,
+}
+
+// values used by versions that didn't store the rules
+fn default_neighbourhood() -> String {
+    "von-neumann".to_string()
+}
+
+fn default_topology() -> String {
+    "bounded".to_string()
+}
+
+fn default_safe_start() -> String {
+    "off".to_string()
+}
+
+fn default_generator() -> String {
+    "random".to_string()
+}
+
+fn default_win_condition() -> String {
+    "flags".to_string()
+}
+
+fn default_safe_regions() -> String {
+    "none".to_string()
+}
+
+impl HistoryEntry {
+    /// Describes the game played on board.
+    pub fn new(board: &Board, result: &str, time: f64, bbbv: (u32, u32), timestamp: u64) -> Self {
+        Self {
+            result: result.to_string(),
+            mines: board.mines_total,
+            cheats: board.cheats_total,
+            size: board.size().to_vec(),
+            neighbourhood: board.rules.neighbourhood.to_string(),
+            topology: board.rules.topology.to_string(),
+            safe_start: board.rules.safe_start.to_string(),
+            seed: Some(board.seed()),
+            generator: board.rules.generator.to_string(),
+            win_condition: board.rules.win_condition.to_string(),
+            practice: board.rules.practice,
+            safe_regions: board.rules.safe_regions.to_string(),
+            undos: board.undos,
+            time: Some((time * 10.0).round() / 10.0),
+            uncovers: board.move_counts.uncovers,
+            chords: board.move_counts.chords,
+            flags: board.move_counts.flags,
+            marks: board.move_counts.marks,
+            cheats_used: Some(board.move_counts.cheats),
+            bbbv: Some(bbbv.0),
+            bbbv_solved: Some(bbbv.1),
+            timestamp: Some(timestamp),
+        }
+    }
+
+    /// Returns the number of moves, including cheats.
+    pub fn move_count(&self) -> u32 {
+        self.uncovers + self.chords + self.flags + self.marks + self.cheats_used.unwrap_or(0)
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.result == "won"
+    }
+
+    /// Returns true if both games have been played using the same board size, number of mines and cheats, and rules.
+    pub fn same_configuration(&self, other: &Self) -> bool {
+        trim_size(&self.size) == trim_size(&other.size)
+            && self.mines == other.mines
+            && self.cheats == other.cheats
+            && self.neighbourhood == other.neighbourhood
+            && self.topology == other.topology
+            && self.safe_start == other.safe_start
+            && self.generator == other.generator
+            && self.win_condition == other.win_condition
+            && self.practice == other.practice
+            && self.safe_regions == other.safe_regions
+    }
+
+    /// Describes the board size, the number of mines and cheats, and the rules.
+    pub fn configuration(&self) -> String {
+        format!(
+            "{}, {} mines, {} cheats, {}, {}, safe start {}, {}, win by {}{}{}",
+            format_size(&self.size),
+            self.mines,
+            self.cheats,
+            self.neighbourhood,
+            self.topology,
+            self.safe_start,
+            self.generator,
+            self.win_condition,
+            if self.practice { ", practice" } else { "" },
+            match self.safe_regions.as_str() {
+                "none" => String::new(),
+                regions => format!(", safe regions {}", regions),
+            }
+        )
+    }
+
+    /// Returns the board size, the number of mines and the neighbourhood used to group games.
+    pub fn group_key(&self) -> (&[usize], u32, &str) {
+        (trim_size(&self.size), self.mines, &self.neighbourhood)
+    }
+
+    /// Appends the entry to the history file, creating the parent directories if necessary.
+    pub fn append(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            let _ = create_dir_all(parent);
+        }
+
+        let mut string = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        string.push('\n');
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(string.as_bytes()))
+            .map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+    }
+
+    /// Reads all entries of the history file, skipping lines that can't be parsed.
+    /// A missing history file contains no entries.
+    pub fn read_all(path: &Path) -> Result<Vec<Self>, String> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let string = read_to_string(path)
+            .map_err(|err| format!("Couldn't read {}: {}", path.display(), err))?;
+
+        Ok(string
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+}
+
+/// Formats a board size given as (x₁, …, xₙ), e.g. "10×10×3", trailing axes of size 1 are omitted.
+pub fn format_size(size: &[usize]) -> String {
+    let size: Vec<String> = trim_size(size).iter().map(|x| x.to_string()).collect();
+
+    size.join("×")
+}
+
+/// Omits the trailing axes of size 1, which don't change the game, keeping at least one axis.
+pub fn trim_size(size: &[usize]) -> &[usize] {
+    let axes = size.iter().rposition(|x| *x > 1).map_or(1, |i| i + 1);
+    &size[..axes.min(size.len())]
+}
+
+/// Statistics of all games played on the same board size with the same number of mines and neighbourhood.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupStatistics {
+    /// Size of the board as (x₁, x₂, …, xₙ) without trailing axes of size 1
+    pub size: Vec<usize>,
+    pub mines: u32,
+    pub neighbourhood: String,
+    pub games: u32,
+    pub wins: u32,
+    /// Number of games won in a row at the end of the history
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// Shortest time of the won games in seconds
+    pub best_time: Option<f64>,
+    /// Average time of the won games in seconds
+    pub average_time: Option<f64>,
+    /// Number of cheats used in all games
+    pub cheats_used: u32,
+}
+
+impl GroupStatistics {
+    /// Returns the percentage of won games.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64 * 100.0
+        }
+    }
+
+    /// Returns true if an entry belongs to this group.
+    pub fn contains(&self, entry: &HistoryEntry) -> bool {
+        entry.group_key()
+            == (
+                self.size.as_slice(),
+                self.mines,
+                self.neighbourhood.as_str(),
+            )
+    }
+
+    /// Computes the statistics of each group in the order in which the groups first appear in entries.
+    pub fn from_entries(entries: &[HistoryEntry]) -> Vec<Self> {
+        let mut groups: Vec<Self> = Vec::new();
+        // sum of the times of the won games that stored a time, and their number
+        let mut times: Vec<(f64, u32)> = Vec::new();
+
+        for entry in entries {
+            let i = match groups.iter().position(|g| g.contains(entry)) {
+                Some(i) => i,
+                None => {
+                    groups.push(Self {
+                        size: trim_size(&entry.size).to_vec(),
+                        mines: entry.mines,
+                        neighbourhood: entry.neighbourhood.clone(),
+                        games: 0,
+                        wins: 0,
+                        current_streak: 0,
+                        best_streak: 0,
+                        best_time: None,
+                        average_time: None,
+                        cheats_used: 0,
+                    });
+                    times.push((0.0, 0));
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[i];
+
+            group.games += 1;
+            group.cheats_used += entry.cheats_used.unwrap_or(0);
+            if entry.is_won() {
+                group.wins += 1;
+                group.current_streak += 1;
+                group.best_streak = group.best_streak.max(group.current_streak);
+
+                if let Some(time) = entry.time {
+                    group.best_time = Some(group.best_time.map_or(time, |t| t.min(time)));
+                    times[i].0 += time;
+                    times[i].1 += 1;
+                }
+            } else {
+                group.current_streak = 0;
+            }
+        }
+
+        for (group, (sum, count)) in groups.iter_mut().zip(times) {
+            if count > 0 {
+                group.average_time = Some(sum / count as f64);
+            }
+        }
+
+        groups
+    }
+}
+
+impl fmt::Display for GroupStatistics {
+    /// Formats the board size, the number of mines and the neighbourhood, e.g. "10×10×3, 15 mines, moore".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}, {} mines, {}",
+            format_size(&self.size),
+            self.mines,
+            self.neighbourhood
+        )
+    }
+}
+
+/// Defines the order in which the statistics are shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    Games,
+    WinRate,
+    BestTime,
+    BestStreak,
+}
+
+impl SortBy {
+    pub const ALL: [Self; 4] = [Self::Games, Self::WinRate, Self::BestTime, Self::BestStreak];
+
+    /// Sorts the statistics, best first. Groups without a best time come last.
+    pub fn sort(self, statistics: &mut [GroupStatistics]) {
+        statistics.sort_by(|a, b| match self {
+            Self::Games => b.games.cmp(&a.games),
+            Self::WinRate => b
+                .win_rate()
+                .partial_cmp(&a.win_rate())
+                .unwrap_or(Ordering::Equal),
+            Self::BestTime => match (a.best_time, b.best_time) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            Self::BestStreak => b.best_streak.cmp(&a.best_streak),
+        });
+    }
+}
+
+impl fmt::Display for SortBy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Games => write!(f, "games"),
+            Self::WinRate => write!(f, "win rate"),
+            Self::BestTime => write!(f, "best time"),
+            Self::BestStreak => write!(f, "best streak"),
+        }
+    }
+}
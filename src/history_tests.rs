@@ -0,0 +1,64 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod history_tests {
+
+    use super::super::{GroupStatistics, HistoryEntry, SortBy};
+
+    fn entry(line: &str) -> HistoryEntry {
+        serde_json::from_str(line).unwrap()
+    }
+
+    #[test]
+    fn test_old_entries() {
+        // written before the rules were stored
+        let e =
+            entry(r#"{"result": "won", "mines": 15, "cheats": 0, "size": [10, 10, 3, 1, 1, 1]}"#);
+        assert!(e.is_won());
+        assert_eq!(e.neighbourhood, "von-neumann");
+        assert_eq!(e.safe_start, "off");
+        assert_eq!(e.win_condition, "flags");
+        assert_eq!(e.time, None);
+        assert_eq!(e.bbbv, None);
+
+        let e = entry(
+            r#"{"result": "lost", "mines": 15, "cheats": 1, "size": [10, 10, 3, 1, 1, 1], "time": 12.5, "cheats_used": 1, "3bv": 40, "3bv_solved": 12}"#,
+        );
+        assert_eq!(e.time, Some(12.5));
+        assert_eq!(e.bbbv, Some(40));
+        assert_eq!(e.bbbv_solved, Some(12));
+    }
+
+    #[test]
+    fn test_statistics() {
+        let game = |result: &str, mines: u32, time: f64| {
+            entry(&format!(
+                r#"{{"result": "{}", "mines": {}, "cheats": 1, "size": [10, 10, 3, 1, 1, 1], "time": {}, "cheats_used": 1}}"#,
+                result, mines, time
+            ))
+        };
+        let entries = vec![
+            game("won", 15, 30.0),
+            game("won", 15, 20.0),
+            game("lost", 15, 5.0),
+            game("won", 20, 50.0),
+            game("won", 15, 40.0),
+        ];
+
+        let mut statistics = GroupStatistics::from_entries(&entries);
+        assert_eq!(statistics.len(), 2);
+
+        let s = &statistics[0];
+        assert_eq!(s.to_string(), "10×10×3, 15 mines, von-neumann");
+        assert_eq!((s.games, s.wins), (4, 3));
+        assert_eq!(s.win_rate(), 75.0);
+        assert_eq!((s.current_streak, s.best_streak), (1, 2));
+        assert_eq!(s.best_time, Some(20.0));
+        assert_eq!(s.average_time, Some(30.0));
+        assert_eq!(s.cheats_used, 4);
+
+        SortBy::WinRate.sort(&mut statistics);
+        assert_eq!(statistics[0].mines, 20);
+        SortBy::Games.sort(&mut statistics);
+        assert_eq!(statistics[0].mines, 15);
+    }
+}
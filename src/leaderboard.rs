@@ -0,0 +1,104 @@
+use crate::{board::MAX_DIMENSIONS, history::HistoryEntry};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::Path,
+};
+
+#[cfg(test)]
+#[path = "leaderboard_tests.rs"]
+mod leaderboard_tests;
+
+/// The fastest won game of each configuration, see `HistoryEntry::same_configuration`.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub records: Vec<HistoryEntry>,
+}
+
+impl Leaderboard {
+    /// Reads the leaderboard from a file, a missing file results in an empty leaderboard.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let string = read_to_string(path)
+            .map_err(|err| format!("Couldn't read {}: {}", path.display(), err))?;
+
+        serde_json::from_str(&string)
+            .map_err(|err| format!("Couldn't parse {}: {}", path.display(), err))
+    }
+
+    /// Writes the leaderboard to a file, creating the parent directories if necessary.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            let _ = create_dir_all(parent);
+        }
+
+        let string = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        write(path, string).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+    }
+
+    /// Replaces the record of the configuration of entry if entry is a faster win.
+    /// Returns None if entry isn't a new record, otherwise the time of the previous record if there was one.
+    pub fn submit(&mut self, entry: &HistoryEntry) -> Option<Option<f64>> {
+        let time = match entry.time {
+            Some(time) if entry.is_won() => time,
+            _ => return None,
+        };
+
+        match self
+            .records
+            .iter_mut()
+            .find(|r| r.same_configuration(entry))
+        {
+            Some(record) => match record.time {
+                Some(best) if best <= time => None,
+                previous => {
+                    *record = entry.clone();
+                    Some(previous)
+                }
+            },
+            None => {
+                self.records.push(entry.clone());
+                Some(None)
+            }
+        }
+    }
+
+    /// Returns the records as comma separated values, including a header.
+    /// There is a column for each of the `MAX_DIMENSIONS` axes, missing axes have size 1.
+    pub fn to_csv(&self) -> String {
+        let axes: Vec<String> = (1..=MAX_DIMENSIONS).map(|i| format!("x{}", i)).collect();
+        let mut csv = format!("{},mines,cheats,neighbourhood,topology,safe_start,generator,win_condition,practice,safe_regions,time,3bv,cheats_used,undos,seed,timestamp\n", axes.join(","));
+
+        for r in &self.records {
+            let optional = |value: Option<String>| value.unwrap_or_default();
+            let size: Vec<String> = (0..MAX_DIMENSIONS)
+                .map(|i| r.size.get(i).unwrap_or(&1).to_string())
+                .collect();
+
+            csv.push_str(&format!(
+                "{},{},{},\"{}\",\"{}\",{},{},{},{},\"{}\",{},{},{},{},{},{}\n",
+                size.join(","),
+                r.mines,
+                r.cheats,
+                r.neighbourhood,
+                r.topology,
+                r.safe_start,
+                r.generator,
+                r.win_condition,
+                r.practice,
+                r.safe_regions,
+                optional(r.time.map(|t| t.to_string())),
+                optional(r.bbbv.map(|b| b.to_string())),
+                optional(r.cheats_used.map(|c| c.to_string())),
+                r.undos,
+                optional(r.seed.map(|s| s.to_string())),
+                optional(r.timestamp.map(|t| t.to_string()))
+            ));
+        }
+
+        csv
+    }
+}
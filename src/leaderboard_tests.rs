@@ -0,0 +1,40 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod leaderboard_tests {
+
+    use super::super::Leaderboard;
+    use crate::history::HistoryEntry;
+
+    fn game(result: &str, mines: u32, time: f64) -> HistoryEntry {
+        serde_json::from_str(&format!(
+            r#"{{"result": "{}", "mines": {}, "cheats": 0, "size": [10, 10, 3, 1, 1, 1], "time": {}}}"#,
+            result, mines, time
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_submit() {
+        let mut leaderboard = Leaderboard::default();
+
+        assert_eq!(leaderboard.submit(&game("won", 15, 30.0)), Some(None));
+        assert_eq!(leaderboard.submit(&game("won", 15, 40.0)), None);
+        assert_eq!(leaderboard.submit(&game("lost", 15, 10.0)), None);
+        assert_eq!(leaderboard.submit(&game("won", 15, 20.0)), Some(Some(30.0)));
+        assert_eq!(leaderboard.submit(&game("won", 16, 50.0)), Some(None));
+
+        assert_eq!(leaderboard.records.len(), 2);
+        assert_eq!(leaderboard.records[0].time, Some(20.0));
+
+        let csv = leaderboard.to_csv();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv
+            .lines()
+            .next()
+            .unwrap()
+            .contains(",practice,safe_regions,time,"));
+        assert!(csv.lines().nth(1).unwrap().starts_with(
+            "10,10,3,1,1,1,1,1,1,1,15,0,\"von-neumann\",\"bounded\",off,random,flags,false,\"none\",20,"
+        ));
+    }
+}
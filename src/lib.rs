@@ -0,0 +1,18 @@
+//! The game engine of mines6d: boards of up to `board::MAX_DIMENSIONS` dimensions with configurable rules,
+//! a solver and an interface for automated players.
+//!
+//! ```
+//! use mines6d::board::{Action, Board, CellState, Rules};
+//!
+//! let mut board = Board::new(&[9, 9], 10, 0, Rules::default(), Some(42));
+//! let center = board.index(&[4, 4]).unwrap();
+//! board.uncover_cell(center);
+//! assert_ne!(board.observe().cell(center), CellState::Covered);
+//! ```
+
+pub mod board;
+pub mod player;
+pub mod solver;
+
+#[cfg(test)]
+mod test_boards;
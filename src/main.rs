@@ -1,24 +1,52 @@
-use clap::{App, Arg};
+use clap::{App, Arg, ArgGroup};
 use cursive::{
     event::{Event, Key},
     traits::{Boxable, Identifiable},
     view::Scrollable,
-    views::{Dialog, DummyView, EditView, LinearLayout, Panel, ScrollView, TextView},
+    views::{
+        Checkbox, Dialog, DummyView, EditView, LinearLayout, Panel, ScrollView, SelectView,
+        TextView,
+    },
     Cursive,
 };
 use directories::ProjectDirs;
 use std::{
-    cmp::max,
     fs::{create_dir, read_to_string, OpenOptions},
-    io::Write,
-    path::Path,
+    io::{stdin, stdout, Write},
+    path::{Path, PathBuf},
     process::exit,
 };
 
-mod board;
+mod batch;
 mod boardview;
+mod history;
+mod leaderboard;
 mod options;
-use options::{Config, Options};
+mod replay;
+mod replayview;
+mod savegame;
+mod settings;
+use history::{HistoryEntry, SortBy};
+use leaderboard::Leaderboard;
+use mines6d::{board, solver};
+use options::{Config, Options, Preset};
+use replay::Replay;
+use savegame::SaveGame;
+use settings::Mines;
+
+// commandline arguments that start a game without showing the main menu
+const GAME_ARGS: [&str; 10] = [
+    "size",
+    "mines",
+    "cheats",
+    "neighbourhood",
+    "topology",
+    "safe-start",
+    "generator",
+    "win-condition",
+    "safe-regions",
+    "practice",
+];
 
 fn main() {
     // parse commandline arguments
@@ -35,6 +63,104 @@ fn main() {
                 .long("default-config")
                 .about("create the default configuration file"),
         )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .takes_value(true)
+                .value_name("SEED")
+                .about("use the given seed to generate the board"),
+        )
+        .arg(
+            Arg::new("load")
+                .long("load")
+                .takes_value(true)
+                .value_name("FILE")
+                .conflicts_with("game")
+                .about("resume the game saved in the given file"),
+        )
+        .arg(
+            Arg::new("replay")
+                .long("replay")
+                .takes_value(true)
+                .value_name("FILE")
+                .conflicts_with_all(&["game", "load"])
+                .about("play back the replay in the given file"),
+        )
+        .arg(
+            Arg::new("size")
+                .long("size")
+                .takes_value(true)
+                .value_name("SIZE")
+                .about("size of each axis from x₁ on, e.g. 10x10x3 for a 3D board (at most 10 axes)"),
+        )
+        .arg(
+            Arg::new("mines")
+                .long("mines")
+                .takes_value(true)
+                .value_name("MINES")
+                .about("number of mines, or a density like 12.5% or 1/8"),
+        )
+        .arg(
+            Arg::new("cheats")
+                .long("cheats")
+                .takes_value(true)
+                .value_name("CHEATS")
+                .about("number of cheats"),
+        )
+        .arg(
+            Arg::new("neighbourhood")
+                .long("neighbourhood")
+                .takes_value(true)
+                .value_name("NEIGHBOURHOOD")
+                .about("e.g. von-neumann, moore, manhattan:2"),
+        )
+        .arg(
+            Arg::new("topology")
+                .long("topology")
+                .takes_value(true)
+                .value_name("TOPOLOGY")
+                .about("e.g. bounded, periodic"),
+        )
+        .arg(
+            Arg::new("safe-start")
+                .long("safe-start")
+                .takes_value(true)
+                .value_name("SAFE_START")
+                .about("off, cell or neighbourhood"),
+        )
+        .arg(
+            Arg::new("generator")
+                .long("generator")
+                .takes_value(true)
+                .value_name("GENERATOR")
+                .about("random or no-guess"),
+        )
+        .arg(
+            Arg::new("win-condition")
+                .long("win-condition")
+                .takes_value(true)
+                .value_name("WIN_CONDITION")
+                .about("flags, uncover or any"),
+        )
+        .arg(
+            Arg::new("safe-regions")
+                .long("safe-regions")
+                .takes_value(true)
+                .value_name("REGIONS")
+                .about("boxes of cells without mines, e.g. 0-2,0-2;5,3"),
+        )
+        .arg(
+            Arg::new("practice")
+                .long("practice")
+                .about("play in practice mode"),
+        )
+        .arg(
+            Arg::new("batch")
+                .long("batch")
+                .conflicts_with_all(&["load", "replay"])
+                .about("play without the user interface, reading commands from stdin and writing JSON to stdout"),
+        )
+        .group(ArgGroup::new("game").args(&GAME_ARGS).multiple(true))
         .get_matches();
 
     // print config and history paths
@@ -78,6 +204,7 @@ fn main() {
             match OpenOptions::new()
                 .write(true)
                 .create(true)
+                .truncate(true)
                 .open(config_path)
             {
                 Ok(mut file) => {
@@ -97,7 +224,75 @@ fn main() {
         exit(0);
     }
 
+    // parse the seed, it is used as the default value in the main menu
+    let seed = args.value_of("seed").map(|seed| {
+        seed.parse::<u64>().unwrap_or_else(|_| {
+            eprintln!("Invalid seed: {}", seed);
+            exit(1);
+        })
+    });
+
+    // parse and validate the settings of a game started from the commandline
+    let settings = if args.is_present("game") || args.is_present("batch") {
+        let mut input = settings::Input::from_config(get_options().config.as_ref());
+        let mut errors = Vec::new();
+
+        if let Some(size) = args.value_of("size") {
+            if let Err(err) = input.set_size(size) {
+                errors.push(err);
+            }
+        }
+        for (name, field) in [
+            ("mines", &mut input.mines),
+            ("cheats", &mut input.cheats),
+            ("neighbourhood", &mut input.neighbourhood),
+            ("topology", &mut input.topology),
+            ("safe-start", &mut input.safe_start),
+            ("generator", &mut input.generator),
+            ("win-condition", &mut input.win_condition),
+            ("safe-regions", &mut input.safe_regions),
+            ("seed", &mut input.seed),
+        ] {
+            if let Some(value) = args.value_of(name) {
+                *field = value.to_string();
+            }
+        }
+        input.practice |= args.is_present("practice");
+
+        match input.parse() {
+            Ok(settings) if errors.is_empty() => Some(settings),
+            result => {
+                errors.extend(result.err().unwrap_or_default());
+                for err in errors {
+                    eprintln!("{}", err);
+                }
+                exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // play the game over stdin and stdout
+    if let Some(settings) = settings.as_ref().filter(|_| args.is_present("batch")) {
+        let board = board::Board::new(
+            &settings.size,
+            settings.mines,
+            settings.cheats,
+            settings.rules.clone(),
+            settings.seed,
+        );
+        if let Err(err) = batch::Batch::new(board).run(stdin().lock(), stdout().lock()) {
+            eprintln!("{}", err);
+            exit(1);
+        }
+        exit(0);
+    }
+
     let mut siv = cursive::default();
+    if let Some(seed) = seed {
+        siv.set_user_data(seed);
+    }
 
     // set cursive theme
     let options = get_options();
@@ -110,10 +305,53 @@ fn main() {
     }
 
     // add global callbacks
-    siv.add_global_callback(Event::Key(Key::Esc), |s| s.quit());
-    siv.add_global_callback(Event::Key(Key::F1), |s| show_help(s));
+    // save the running game when the program is closed
+    let save_and_quit = |s: &mut Cursive| {
+        s.call_on_name("boardview", |view: &mut boardview::BoardView| view.save());
+        s.quit();
+    };
+    siv.add_global_callback(Event::Key(Key::Esc), save_and_quit);
+    siv.clear_global_callbacks(Event::CtrlChar('c'));
+    siv.add_global_callback(Event::CtrlChar('c'), save_and_quit);
+    siv.add_global_callback(Event::Key(Key::F1), show_help);
+
+    // play back a replay, resume a saved game or show the main menu
+    if let Some(path) = args.value_of("replay") {
+        // the replayed game must not be stored
+        let mut options = get_options();
+        options.history_path = None;
+        options.save_path = None;
+        options.replay_dir = None;
 
-    show_main_menu(&mut siv);
+        match Replay::read(Path::new(path)).and_then(|replay| {
+            boardview::BoardView::from_replay(&replay, options)
+                .map(|bv| replayview::ReplayView::new(bv, replay))
+        }) {
+            Ok(rv) => {
+                siv.add_layer(Panel::new(ScrollView::new(rv).scroll_x(true)));
+                siv.set_autorefresh(true);
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                exit(1);
+            }
+        }
+    } else if let Some(path) = args.value_of("load") {
+        let path = PathBuf::from(path);
+        match SaveGame::read(&path)
+            .and_then(|save| boardview::BoardView::from_save(&save, path, get_options()))
+        {
+            Ok(bv) => show_boardview(&mut siv, bv),
+            Err(err) => {
+                eprintln!("{}", err);
+                exit(1);
+            }
+        }
+    } else if let Some(settings) = settings {
+        show_board(&mut siv, settings);
+    } else {
+        show_main_menu(&mut siv);
+    }
     siv.run();
 }
 
@@ -126,10 +364,22 @@ fn get_options() -> Options {
         let mut history_path = project_dirs.data_dir().to_path_buf();
         history_path.push("history.json");
 
+        let mut save_path = project_dirs.data_dir().to_path_buf();
+        save_path.push("save.json");
+
+        let mut leaderboard_path = project_dirs.data_dir().to_path_buf();
+        leaderboard_path.push("leaderboard.json");
+
+        let mut replay_dir = project_dirs.data_dir().to_path_buf();
+        replay_dir.push("replays");
+
         let mut config_path = project_dirs.config_dir().to_path_buf();
         config_path.push("config.json");
 
         options.history_path = Some(history_path);
+        options.save_path = Some(save_path);
+        options.leaderboard_path = Some(leaderboard_path);
+        options.replay_dir = Some(replay_dir);
         options.config_path = Some(config_path);
     };
 
@@ -147,89 +397,543 @@ fn get_options() -> Options {
 
 // shows the main menu
 fn show_main_menu(s: &mut Cursive) {
+    let save_path = get_options().save_path.filter(|path| path.exists());
+
+    let mut input = settings::Input::from_config(get_options().config.as_ref());
+    if let Some(seed) = s.user_data::<u64>() {
+        input.seed = seed.to_string();
+    }
+
+    // built-in presets followed by those saved in the config file
+    let mut presets = SelectView::new().popup();
+    presets.add_item("custom", None);
+    for preset in Preset::builtin()
+        .into_iter()
+        .chain(get_options().config.map(|c| c.presets).unwrap_or_default())
+    {
+        presets.add_item(preset.name.clone(), Some(preset));
+    }
+
+    let mut dimensions = SelectView::new().popup();
+    for i in 1..=board::MAX_DIMENSIONS {
+        dimensions.add_item(i.to_string(), i);
+    }
+    dimensions.set_selection(input.size.len() - 1);
+
+    let mut dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(
+                LinearLayout::vertical()
+                    .child(TextView::new("Press F1 for help"))
+                    .child(TextView::new("Press Esc to exit"))
+                    .child(DummyView.fixed_height(1))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("Preset "))
+                            .child(
+                                presets
+                                    .on_submit(|s, preset: &Option<Preset>| {
+                                        if let Some(preset) = preset {
+                                            apply_preset(s, preset);
+                                        }
+                                    })
+                                    .with_name("select_preset"),
+                            ),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(TextView::new("Dimensions "))
+                            .child(
+                                dimensions
+                                    .on_submit(|s, dimensions: &usize| {
+                                        let mut input = read_input(s);
+                                        input.set_dimensions(*dimensions);
+                                        set_size_fields(s, &input.size);
+                                    })
+                                    .with_name("select_dimensions"),
+                            ),
+                    )
+                    .child(size_fields(&input.size).with_name("layout_sizes"))
+                    .child(TextView::new("Mines (e.g. 15, 12.5% or 1/8)"))
+                    .child(
+                        EditView::new()
+                            .content(input.mines)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_mines"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Cheats"))
+                    .child(
+                        EditView::new()
+                            .content(input.cheats)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_cheats"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Neighbourhood"))
+                    .child(
+                        EditView::new()
+                            .content(input.neighbourhood)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_neighbourhood"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Topology"))
+                    .child(
+                        EditView::new()
+                            .content(input.topology)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_topology"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Safe start"))
+                    .child(
+                        EditView::new()
+                            .content(input.safe_start)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_safe_start"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Generator"))
+                    .child(
+                        EditView::new()
+                            .content(input.generator)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_generator"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Win condition"))
+                    .child(
+                        EditView::new()
+                            .content(input.win_condition)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_win_condition"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Safe regions (e.g. 0-2,0-2;5,3)"))
+                    .child(
+                        EditView::new()
+                            .content(input.safe_regions)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_safe_regions"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(TextView::new("Seed (empty for a random board)"))
+                    .child(
+                        EditView::new()
+                            .content(input.seed)
+                            .on_edit(|s, _, _| update_summary(s))
+                            .with_name("edit_seed"),
+                    )
+                    .child(DummyView.fixed_height(1))
+                    .child(
+                        LinearLayout::horizontal()
+                            .child(
+                                Checkbox::new()
+                                    .with_checked(input.practice)
+                                    .with_name("checkbox_practice"),
+                            )
+                            .child(TextView::new(" Practice mode")),
+                    )
+                    .scrollable(),
+            )
+            .child(DummyView.fixed_height(1))
+            .child(TextView::new("").with_name("text_summary")),
+    )
+    .title("nD Minesweeper")
+    .button("info", |s| match read_input(s).parse() {
+        Ok(settings) => show_info(s, &settings),
+        Err(errors) => show_error(s, &errors.join("\n")),
+    })
+    .button("save preset", show_save_preset)
+    .button("statistics", show_statistics)
+    .button("records", show_leaderboard)
+    .button("start", |s| match read_input(s).parse() {
+        Ok(settings) => {
+            s.pop_layer();
+            show_board(s, settings);
+        }
+        Err(errors) => show_error(s, &errors.join("\n")),
+    });
+
+    // resume the saved game
+    if let Some(save_path) = save_path {
+        dialog.add_button("continue", move |s| {
+            match SaveGame::read(&save_path).and_then(|save| {
+                boardview::BoardView::from_save(&save, save_path.clone(), get_options())
+            }) {
+                Ok(bv) => {
+                    s.pop_layer();
+                    show_boardview(s, bv);
+                }
+                Err(err) => show_error(s, &err),
+            }
+        });
+    }
+
+    s.add_layer(dialog);
+    update_summary(s);
+}
+
+// creates a field for the size of each axis
+fn size_fields(size: &[String]) -> LinearLayout {
+    let mut layout = LinearLayout::vertical();
+
+    for (i, x) in size.iter().enumerate() {
+        layout.add_child(DummyView.fixed_height(1));
+        layout.add_child(TextView::new(format!("Size of {}", settings::axis_name(i))));
+        layout.add_child(
+            EditView::new()
+                .content(x.clone())
+                .on_edit(|s, _, _| update_summary(s))
+                .with_name(format!("edit_x{}", i + 1)),
+        );
+    }
+
+    layout
+}
+
+// replaces the size fields of the main menu, one per dimension
+fn set_size_fields(s: &mut Cursive, size: &[String]) {
+    s.call_on_name("layout_sizes", |view: &mut LinearLayout| {
+        *view = size_fields(size)
+    });
+    s.call_on_name("select_dimensions", |view: &mut SelectView<usize>| {
+        view.set_selection(size.len() - 1)
+    });
+    update_summary(s);
+}
+
+// fills the main menu with the values of a preset
+fn apply_preset(s: &mut Cursive, preset: &Preset) {
+    let size: Vec<String> = preset.size.iter().map(|x| x.to_string()).collect();
+    set_size_fields(s, &size);
+
+    let mut set = |name: &str, content: String| {
+        s.call_on_name(name, |view: &mut EditView| view.set_content(content));
+    };
+
+    set("edit_mines", preset.mines.to_string());
+    set("edit_cheats", preset.cheats.to_string());
+
+    for (name, value) in [
+        ("edit_neighbourhood", &preset.neighbourhood),
+        ("edit_topology", &preset.topology),
+        ("edit_safe_start", &preset.safe_start),
+        ("edit_generator", &preset.generator),
+        ("edit_win_condition", &preset.win_condition),
+        ("edit_safe_regions", &preset.safe_regions),
+    ] {
+        if let Some(value) = value {
+            set(name, value.clone());
+        }
+    }
+
+    if let Some(practice) = preset.practice {
+        s.call_on_name("checkbox_practice", |view: &mut Checkbox| {
+            view.set_checked(practice)
+        });
+    }
+
+    update_summary(s);
+}
+
+// reads the settings of a new game from the main menu
+fn read_input(s: &mut Cursive) -> settings::Input {
+    // there is a field for each dimension
+    let size = (1..=board::MAX_DIMENSIONS)
+        .map_while(|i| {
+            s.call_on_name(&format!("edit_x{}", i), |view: &mut EditView| {
+                view.get_content().to_string()
+            })
+        })
+        .collect();
+
+    let mut get = |name: &str| {
+        s.call_on_name(name, |view: &mut EditView| view.get_content().to_string())
+            .unwrap()
+    };
+
+    settings::Input {
+        size,
+        mines: get("edit_mines"),
+        cheats: get("edit_cheats"),
+        neighbourhood: get("edit_neighbourhood"),
+        topology: get("edit_topology"),
+        safe_start: get("edit_safe_start"),
+        generator: get("edit_generator"),
+        win_condition: get("edit_win_condition"),
+        safe_regions: get("edit_safe_regions"),
+        seed: get("edit_seed"),
+        practice: s
+            .call_on_name("checkbox_practice", |view: &mut Checkbox| view.is_checked())
+            .unwrap(),
+    }
+}
+
+// describes the board below the main menu while the settings are being entered
+fn update_summary(s: &mut Cursive) {
+    let summary = read_input(s).summary();
+    s.call_on_name("text_summary", |view: &mut TextView| {
+        view.set_content(summary)
+    });
+}
+
+// asks for a name and saves the values of the main menu as a preset in the config file
+fn show_save_preset(s: &mut Cursive) {
     s.add_layer(
         Dialog::around(
             LinearLayout::vertical()
-                .child(TextView::new("Press F1 for help"))
-                .child(TextView::new("Press Esc to exit"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Size of x₁"))
-                .child(EditView::new().content("10").with_name("edit_x1"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Size of x₂"))
-                .child(EditView::new().content("10").with_name("edit_x2"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Size of x₃"))
-                .child(EditView::new().content("3").with_name("edit_x3"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Size of x₄"))
-                .child(EditView::new().content("1").with_name("edit_x4"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Size of x₅"))
-                .child(EditView::new().content("1").with_name("edit_x5"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Size of x₆"))
-                .child(EditView::new().content("1").with_name("edit_x6"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Mines"))
-                .child(EditView::new().content("15").with_name("edit_mines"))
-                .child(DummyView.fixed_height(1))
-                .child(TextView::new("Cheats"))
-                .child(EditView::new().content("0").with_name("edit_cheats"))
-                .scrollable(),
+                .child(TextView::new("Name"))
+                .child(EditView::new().with_name("edit_preset_name")),
         )
-        .title("6D Minesweeper")
-        .button("info", |s| {
-            let x6 = max(get_editview_as(s, "edit_x6", 1), 1);
-            let x5 = max(get_editview_as(s, "edit_x5", 1), 1);
-            let x4 = max(get_editview_as(s, "edit_x4", 1), 1);
-            let x3 = max(get_editview_as(s, "edit_x3", 3), 1);
-            let x2 = max(get_editview_as(s, "edit_x2", 10), 1);
-            let x1 = max(get_editview_as(s, "edit_x1", 10), 1);
-            let mines = get_editview_as(s, "edit_mines", 15);
-            let cheats = get_editview_as(s, "edit_cheats", 0);
-
-            //s.pop_layer();
-            show_info(s, (x6, x5, x4, x3, x2, x1), mines, cheats);
+        .title("Save preset")
+        .button("save", |s| {
+            let name = s
+                .call_on_name("edit_preset_name", |view: &mut EditView| view.get_content())
+                .unwrap();
+            if name.trim().is_empty() {
+                show_error(s, "The preset requires a name");
+                return;
+            }
+
+            let input = read_input(s);
+            let settings = match input.parse() {
+                Ok(settings) => settings,
+                Err(errors) => {
+                    show_error(s, &errors.join("\n"));
+                    return;
+                }
+            };
+            let preset = Preset {
+                name: name.trim().to_string(),
+                size: settings.size.clone(),
+                mines: input.mines.parse().unwrap_or(Mines::Count(settings.mines)),
+                cheats: settings.cheats,
+                neighbourhood: Some(input.neighbourhood),
+                topology: Some(input.topology),
+                safe_start: Some(input.safe_start),
+                generator: Some(input.generator),
+                win_condition: Some(input.win_condition),
+                practice: Some(input.practice),
+                safe_regions: Some(input.safe_regions),
+            };
+
+            let options = get_options();
+            let config_path = match options.config_path {
+                Some(path) => path,
+                None => {
+                    show_error(s, "The config path is undefined");
+                    return;
+                }
+            };
+            let mut config = options
+                .config
+                .unwrap_or_else(|| serde_json::from_str::<Config>("{}").unwrap());
+
+            // a preset having the same name is replaced
+            config.presets.retain(|p| p.name != preset.name);
+            config.presets.push(preset.clone());
+
+            match config.write(&config_path) {
+                Ok(()) => {
+                    s.pop_layer();
+                    s.call_on_name("select_preset", |view: &mut SelectView<Option<Preset>>| {
+                        let existing = view
+                            .iter()
+                            .position(|(_, p)| p.as_ref().map(|p| &p.name) == Some(&preset.name));
+                        match existing {
+                            Some(i) => {
+                                view.remove_item(i);
+                                view.insert_item(i, preset.name.clone(), Some(preset));
+                                view.set_selection(i)
+                            }
+                            None => {
+                                view.add_item(preset.name.clone(), Some(preset));
+                                view.set_selection(view.len() - 1)
+                            }
+                        }
+                    });
+                }
+                Err(err) => show_error(s, &err),
+            }
         })
-        .button("start", |s| {
-            let x6 = max(get_editview_as(s, "edit_x6", 1), 1);
-            let x5 = max(get_editview_as(s, "edit_x5", 1), 1);
-            let x4 = max(get_editview_as(s, "edit_x4", 1), 1);
-            let x3 = max(get_editview_as(s, "edit_x3", 3), 1);
-            let x2 = max(get_editview_as(s, "edit_x2", 10), 1);
-            let x1 = max(get_editview_as(s, "edit_x1", 10), 1);
-            let mines = get_editview_as(s, "edit_mines", 15);
-            let cheats = get_editview_as(s, "edit_cheats", 0);
+        .button("cancel", |s| {
+            s.pop_layer();
+        }),
+    );
+}
 
+// shows the statistics of the games in the history file
+fn show_statistics(s: &mut Cursive) {
+    let mut sort = SelectView::new().popup();
+    for sort_by in &SortBy::ALL {
+        sort.add_item(sort_by.to_string(), *sort_by);
+    }
+
+    s.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new("Filter"))
+                .child(
+                    EditView::new()
+                        .on_edit(|s, _, _| update_statistics(s))
+                        .with_name("edit_statistics_filter"),
+                )
+                .child(DummyView.fixed_height(1))
+                .child(
+                    LinearLayout::horizontal()
+                        .child(TextView::new("Sort by "))
+                        .child(
+                            sort.on_submit(|s, _| update_statistics(s))
+                                .with_name("select_statistics_sort"),
+                        ),
+                )
+                .child(DummyView.fixed_height(1))
+                .child(TextView::new(format!(
+                    "{:<28} {:>5} {:>6} {:>7} {:>9} {:>9} {:>6}",
+                    "Board", "Games", "Won", "Streak", "Best", "Average", "Cheats"
+                )))
+                .child(
+                    SelectView::<history::GroupStatistics>::new()
+                        .on_submit(show_group_history)
+                        .with_name("select_statistics")
+                        .scrollable(),
+                ),
+        )
+        .title("Statistics")
+        .button("back", |s| {
             s.pop_layer();
-            show_board(s, (x6, x5, x4, x3, x2, x1), mines, cheats);
         }),
     );
+
+    update_statistics(s);
+}
+
+// fills the statistics table according to the filter and sort order
+fn update_statistics(s: &mut Cursive) {
+    let entries = match get_options().history_path {
+        Some(path) => match HistoryEntry::read_all(&path) {
+            Ok(entries) => entries,
+            Err(err) => {
+                show_error(s, &err);
+                return;
+            }
+        },
+        None => Vec::new(),
+    };
+
+    let filter = s
+        .call_on_name("edit_statistics_filter", |view: &mut EditView| {
+            view.get_content()
+        })
+        .unwrap()
+        .to_lowercase();
+    let sort_by = s
+        .call_on_name("select_statistics_sort", |view: &mut SelectView<SortBy>| {
+            view.selection()
+        })
+        .unwrap()
+        .map_or(SortBy::Games, |sort_by| *sort_by);
+
+    let mut statistics: Vec<history::GroupStatistics> =
+        history::GroupStatistics::from_entries(&entries)
+            .into_iter()
+            .filter(|g| g.to_string().to_lowercase().contains(filter.trim()))
+            .collect();
+    sort_by.sort(&mut statistics);
+
+    let format_time = |time: Option<f64>| time.map_or("-".to_string(), |t| format!("{:.1} s", t));
+
+    s.call_on_name(
+        "select_statistics",
+        |view: &mut SelectView<history::GroupStatistics>| {
+            view.clear();
+            for group in statistics {
+                let label = format!(
+                    "{:<28} {:>5} {:>5.0}% {:>3}/{:<3} {:>9} {:>9} {:>6}",
+                    group.to_string(),
+                    group.games,
+                    group.win_rate(),
+                    group.current_streak,
+                    group.best_streak,
+                    format_time(group.best_time),
+                    format_time(group.average_time),
+                    group.cheats_used
+                );
+                view.add_item(label, group);
+            }
+        },
+    );
+}
+
+// shows all games of a group of the statistics
+fn show_group_history(s: &mut Cursive, group: &history::GroupStatistics) {
+    let entries = get_options()
+        .history_path
+        .and_then(|path| HistoryEntry::read_all(&path).ok())
+        .unwrap_or_default();
+
+    let mut list = LinearLayout::vertical().child(TextView::new(format!(
+        "{:<6} {:>9} {:>9} {:>7} {:>6}  {}",
+        "Result", "Time", "3BV", "Cheats", "Undos", "Rules"
+    )));
+    for entry in entries.iter().filter(|e| group.contains(e)).rev() {
+        let bbbv = match (entry.bbbv_solved, entry.bbbv) {
+            (Some(solved), Some(total)) => format!("{}/{}", solved, total),
+            _ => "-".to_string(),
+        };
+
+        list.add_child(TextView::new(format!(
+            "{:<6} {:>9} {:>9} {:>7} {:>6}  {}, {}{}",
+            entry.result,
+            entry
+                .time
+                .map_or("-".to_string(), |t| format!("{:.1} s", t)),
+            bbbv,
+            entry
+                .cheats_used
+                .map_or("-".to_string(), |c| format!("{}/{}", c, entry.cheats)),
+            entry.undos,
+            entry.topology,
+            entry.generator,
+            if entry.practice { ", practice" } else { "" }
+        )));
+    }
+
+    s.add_layer(
+        Dialog::around(list.scrollable())
+            .title(group.to_string())
+            .button("back", |s| {
+                s.pop_layer();
+            }),
+    );
 }
 
 // shows more detailed information about the current settings
-fn show_info(
-    s: &mut Cursive,
-    size: (usize, usize, usize, usize, usize, usize),
-    mines: u32,
-    cheats: u32,
-) {
-    let (x6, x5, x4, x3, x2, x1) = size;
-    let cells = (x1 * x2 * x3 * x4 * x5 * x6) as u32;
-    let mines_percent = (mines as f64 / cells as f64) * 100 as f64;
-    let cells_per_mine = cells / mines;
+fn show_info(s: &mut Cursive, settings: &settings::Settings) {
+    let (cells, mines, rules) = (settings.cells(), settings.mines, &settings.rules);
+    let mines_percent = (mines as f64 / cells as f64) * 100_f64;
+    let cells_per_mine = if mines == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}", cells as f64 / mines as f64)
+    };
 
     s.add_layer(
         Dialog::around(
             LinearLayout::vertical()
                 .child(TextView::new(format!(
-                    "Board size:          ({}, {}, {}, {}, {}, {})",
-                    x1, x2, x3, x4, x5, x6
+                    "Board size:          {}",
+                    boardview::BoardView::format_coordinates(&settings.size)
                 )))
                 .child(TextView::new(format!("Number of cells:     {}", cells)))
                 .child(TextView::new(format!("Number of mines:     {}", mines)))
-                .child(TextView::new(format!("Number of cheats:    {}", cheats)))
+                .child(TextView::new(format!(
+                    "Number of cheats:    {}",
+                    settings.cheats
+                )))
                 .child(TextView::new(format!(
                     "Percentage of mines: {:.3} %",
                     mines_percent
@@ -238,6 +942,34 @@ fn show_info(
                     "Cells per mine:      {}",
                     cells_per_mine
                 )))
+                .child(TextView::new(format!(
+                    "Neighbourhood:       {}",
+                    rules.neighbourhood
+                )))
+                .child(TextView::new(format!(
+                    "Max. neighbours:     {}",
+                    rules.neighbourhood.max_neighbors(&settings.size)
+                )))
+                .child(TextView::new(format!(
+                    "Topology:            {}",
+                    rules.topology
+                )))
+                .child(TextView::new(format!(
+                    "Safe start:          {}",
+                    rules.safe_start
+                )))
+                .child(TextView::new(format!(
+                    "Generator:           {}",
+                    rules.generator
+                )))
+                .child(TextView::new(format!(
+                    "Win condition:       {}",
+                    rules.win_condition
+                )))
+                .child(TextView::new(format!(
+                    "Practice mode:       {}",
+                    if rules.practice { "yes" } else { "no" }
+                )))
                 .scrollable(),
         )
         .title("Info")
@@ -249,27 +981,28 @@ fn show_info(
 
 // shows the help dialog
 fn show_help(s: &mut Cursive) {
-    // remove callbacks to prevent the winning/losing dialogs being shown accidentally
-    s.clear_global_callbacks('f');
-    s.clear_global_callbacks('c');
-    s.clear_global_callbacks(' ');
+    // pause the clock of a running game
+    s.call_on_name("boardview", |view: &mut boardview::BoardView| view.pause());
 
     s.add_layer(
         Dialog::around(TextView::new(include_str!("help.txt")).scrollable())
             .title("Help")
             .button("ok", |s| {
                 s.pop_layer();
+                s.call_on_name("boardview", |view: &mut boardview::BoardView| view.resume());
             }),
     );
 }
 
+// shows an error message
+fn show_error(s: &mut Cursive, message: &str) {
+    s.add_layer(Dialog::text(message).title("Error").button("ok", |s| {
+        s.pop_layer();
+    }));
+}
+
 // shows the "you lost" dialog
 fn show_lost(s: &mut Cursive) {
-    // remove callbacks to prevent the winning/losing dialogs being shown accidentally
-    s.clear_global_callbacks('f');
-    s.clear_global_callbacks('c');
-    s.clear_global_callbacks(' ');
-
     s.add_layer(
         Dialog::text("Return to the main menu")
             .title("You lost")
@@ -280,52 +1013,169 @@ fn show_lost(s: &mut Cursive) {
     );
 }
 
-// shows the "you won" dialog
-fn show_won(s: &mut Cursive) {
+// shows the "you won" dialog, announcing a new record
+fn show_won(s: &mut Cursive, entry: &HistoryEntry) {
+    let mut text = "Return to the main menu".to_string();
+
+    if let Some(path) = get_options().leaderboard_path {
+        match Leaderboard::read(&path) {
+            Ok(mut leaderboard) => {
+                if let Some(previous) = leaderboard.submit(entry) {
+                    let _ = leaderboard.write(&path);
+                    text = match previous {
+                        Some(previous) => format!(
+                            "New record: {:.1} s, previously {:.1} s\n\n{}",
+                            entry.time.unwrap_or_default(),
+                            previous,
+                            text
+                        ),
+                        None => format!(
+                            "New record: {:.1} s, the first win with this configuration\n\n{}",
+                            entry.time.unwrap_or_default(),
+                            text
+                        ),
+                    };
+                }
+            }
+            Err(err) => text = format!("{}\n\n{}", err, text),
+        }
+    }
+
+    s.add_layer(Dialog::text(text).title("You won").button("ok", |s| {
+        s.pop_layer();
+        show_main_menu(s);
+    }));
+}
+
+// shows the fastest win of each configuration
+fn show_leaderboard(s: &mut Cursive) {
+    let path = get_options().leaderboard_path;
+    let leaderboard = match &path {
+        Some(path) => match Leaderboard::read(path) {
+            Ok(leaderboard) => leaderboard,
+            Err(err) => {
+                show_error(s, &err);
+                return;
+            }
+        },
+        None => Leaderboard::default(),
+    };
+
+    let mut records = leaderboard.records.clone();
+    records.sort_by_key(|record| record.configuration());
+
+    let mut list = LinearLayout::vertical();
+    if records.is_empty() {
+        list.add_child(TextView::new("No games have been won yet"));
+    }
+    for record in records {
+        let efficiency = match (record.bbbv_solved, record.move_count()) {
+            (Some(bbbv), moves) if moves > 0 => {
+                format!(", efficiency {:.0} %", bbbv as f64 / moves as f64 * 100.0)
+            }
+            _ => "".to_string(),
+        };
+
+        list.add_child(TextView::new(record.configuration()));
+        list.add_child(TextView::new(format!(
+            "  {:.1} s{}",
+            record.time.unwrap_or_default(),
+            efficiency
+        )));
+    }
+
+    let export_path = path
+        .map(|path| path.with_extension("csv"))
+        .unwrap_or_else(|| PathBuf::from("leaderboard.csv"));
+
     s.add_layer(
-        Dialog::text("Return to the main menu")
-            .title("You won")
-            .button("ok", |s| {
-                s.pop_layer();
-                show_main_menu(s);
-            }),
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(list.scrollable())
+                .child(DummyView.fixed_height(1))
+                .child(TextView::new("Export to"))
+                .child(
+                    EditView::new()
+                        .content(export_path.display().to_string())
+                        .with_name("edit_leaderboard_export"),
+                ),
+        )
+        .title("Records")
+        .button("export", move |s| {
+            let path = s
+                .call_on_name("edit_leaderboard_export", |view: &mut EditView| {
+                    view.get_content()
+                })
+                .unwrap();
+
+            match std::fs::write(path.as_str(), leaderboard.to_csv()) {
+                Ok(()) => {
+                    s.add_layer(
+                        Dialog::text(format!("Exported to {}", path))
+                            .title("Records")
+                            .button("ok", |s| {
+                                s.pop_layer();
+                            }),
+                    );
+                }
+                Err(err) => show_error(s, &format!("Couldn't write {}: {}", path, err)),
+            }
+        })
+        .button("back", |s| {
+            s.pop_layer();
+        }),
     );
 }
 
 // shows the board
-fn show_board(
-    s: &mut Cursive,
-    size: (usize, usize, usize, usize, usize, usize),
-    mines: u32,
-    cheats: u32,
-) {
-    // add the BoardView
-    let bv = boardview::BoardView::new(size, mines, cheats, get_options());
+fn show_board(s: &mut Cursive, settings: settings::Settings) {
+    let bv = boardview::BoardView::new(
+        &settings.size,
+        settings.mines,
+        settings.cheats,
+        settings.rules,
+        settings.seed,
+        get_options(),
+    );
+    show_boardview(s, bv);
+}
+
+// shows a BoardView
+fn show_boardview(s: &mut Cursive, bv: boardview::BoardView) {
+    // add the BoardView, refreshing it to show the clock
     s.add_layer(Panel::new(
         ScrollView::new(bv.with_name("boardview")).scroll_x(true),
     ));
+    s.set_fps(2);
 
-    // add callbacks
-    s.add_global_callback(Event::Char(' '), |s| {
-        s.pop_layer();
-        show_lost(s);
-    });
+    // add callbacks, removing those of previous games
+    for c in [' ', 'x', 'f', 'c', 'z', 'y'] {
+        s.clear_global_callbacks(c);
+        s.add_global_callback(Event::Char(c), show_result);
+    }
+}
 
-    s.add_global_callback(Event::Char('f'), |s| {
-        s.pop_layer();
-        show_won(s);
-    });
+// shows the "you won" or "you lost" dialog once the game has ended
+fn show_result(s: &mut Cursive) {
+    let result = s
+        .call_on_name("boardview", |view: &mut boardview::BoardView| view.result())
+        .flatten();
 
-    s.add_global_callback(Event::Char('c'), |s| {
-        s.pop_layer();
-        show_won(s);
-    });
+    match result {
+        Some(entry) if entry.is_won() => {
+            pop_boardview(s);
+            show_won(s, &entry);
+        }
+        Some(_) => {
+            pop_boardview(s);
+            show_lost(s);
+        }
+        None => (),
+    }
 }
 
-// returns the current value of the EditView having the given name
-fn get_editview_as<T: std::str::FromStr>(s: &mut Cursive, name: &str, default: T) -> T {
-    s.call_on_name(name, |view: &mut EditView| view.get_content())
-        .unwrap()
-        .parse::<T>()
-        .unwrap_or(default)
+// removes the BoardView, the clock no longer needs to be refreshed
+fn pop_boardview(s: &mut Cursive) {
+    s.pop_layer();
+    s.set_fps(0);
 }
@@ -1,9 +1,22 @@
+use crate::settings::Mines;
 use serde::{Deserialize, Serialize};
-use std::{option::Option, path::PathBuf};
+use std::{
+    fs::{create_dir_all, write},
+    option::Option,
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+#[path = "options_tests.rs"]
+mod options_tests;
 
 /// Used to pass around global options
 pub struct Options {
     pub history_path: Option<PathBuf>,
+    pub save_path: Option<PathBuf>,
+    pub leaderboard_path: Option<PathBuf>,
+    /// Directory the replays of finished games are written to
+    pub replay_dir: Option<PathBuf>,
     pub config_path: Option<PathBuf>,
     pub config: Option<Config>,
 }
@@ -12,6 +25,9 @@ impl Options {
     pub const fn new() -> Self {
         Self {
             history_path: None,
+            save_path: None,
+            leaderboard_path: None,
+            replay_dir: None,
             config_path: None,
             config: None,
         }
@@ -29,6 +45,108 @@ pub struct Config {
     pub use_color: bool,
     #[serde(default = "default_theme_file")]
     pub theme_file: String,
+    #[serde(default = "default_neighbourhood")]
+    pub neighbourhood: String,
+    #[serde(default = "default_topology")]
+    pub topology: String,
+    #[serde(default = "default_safe_start")]
+    pub safe_start: String,
+    #[serde(default = "default_generator")]
+    pub generator: String,
+    #[serde(default = "default_win_condition")]
+    pub win_condition: String,
+    #[serde(default = "default_practice")]
+    pub practice: bool,
+    /// Presets saved from the main menu
+    #[serde(default)]
+    pub presets: Vec<Preset>,
+}
+
+impl Config {
+    /// Writes the config file, creating the parent directories if necessary.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            let _ = create_dir_all(parent);
+        }
+
+        let mut string = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        string.push('\n');
+        write(path, string).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+    }
+}
+
+/// Named settings of the main menu.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    /// Size of the board as (x₁, x₂, …, xₙ)
+    pub size: Vec<usize>,
+    /// A count or a density, which keeps the preset useful when the size is changed
+    pub mines: Mines,
+    #[serde(default)]
+    pub cheats: u32,
+    /// The rules are left unchanged if they are None
+    #[serde(default)]
+    pub neighbourhood: Option<String>,
+    #[serde(default)]
+    pub topology: Option<String>,
+    #[serde(default)]
+    pub safe_start: Option<String>,
+    #[serde(default)]
+    pub generator: Option<String>,
+    #[serde(default)]
+    pub win_condition: Option<String>,
+    #[serde(default)]
+    pub practice: Option<bool>,
+    #[serde(default)]
+    pub safe_regions: Option<String>,
+}
+
+impl Preset {
+    /// Creates a preset that leaves the rules unchanged.
+    fn new(name: &str, size: &[usize], mines: Mines, cheats: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            size: size.to_vec(),
+            mines,
+            cheats,
+            neighbourhood: None,
+            topology: None,
+            safe_start: None,
+            generator: None,
+            win_condition: None,
+            practice: None,
+            safe_regions: None,
+        }
+    }
+
+    /// Returns the built-in presets, the density of mines decreases with the number of dimensions
+    /// because each cell has more neighbours.
+    pub fn builtin() -> Vec<Self> {
+        vec![
+            Self::new("beginner 2D", &[9, 9], Mines::Percentage(12.3), 0),
+            Self::new("intermediate 2D", &[16, 16], Mines::Percentage(15.6), 0),
+            Self::new("expert 2D", &[30, 16], Mines::Percentage(20.6), 0),
+            Self::new("beginner 3D", &[6, 6, 3], Mines::Percentage(9.3), 0),
+            Self::new("intermediate 3D", &[10, 10, 3], Mines::Percentage(10.0), 0),
+            Self::new("expert 3D", &[10, 10, 5], Mines::Percentage(14.0), 1),
+            Self::new("expert 4D", &[6, 6, 4, 3], Mines::Percentage(10.4), 2),
+            Self::new("expert 5D", &[5, 5, 3, 3, 3], Mines::Percentage(8.9), 3),
+            Self::new("expert 6D", &[4, 4, 3, 3, 3, 3], Mines::Percentage(7.7), 4),
+            Self::new(
+                "expert 7D",
+                &[4, 3, 3, 3, 3, 3, 3],
+                Mines::Percentage(6.9),
+                5,
+            ),
+            Self::new(
+                "expert 8D",
+                &[3, 3, 3, 3, 3, 3, 3, 3],
+                Mines::Percentage(6.1),
+                6,
+            ),
+        ]
+    }
 }
 
 // default config values
@@ -47,3 +165,27 @@ const fn default_use_color() -> bool {
 fn default_theme_file() -> String {
     "".to_string()
 }
+
+fn default_neighbourhood() -> String {
+    "von-neumann".to_string()
+}
+
+fn default_topology() -> String {
+    "bounded".to_string()
+}
+
+fn default_safe_start() -> String {
+    "cell".to_string()
+}
+
+fn default_generator() -> String {
+    "random".to_string()
+}
+
+fn default_win_condition() -> String {
+    "any".to_string()
+}
+
+const fn default_practice() -> bool {
+    false
+}
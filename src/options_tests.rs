@@ -0,0 +1,67 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod options_tests {
+
+    use super::super::{Config, Preset};
+    use crate::settings::Mines;
+
+    #[test]
+    fn test_builtin_presets() {
+        let presets = Preset::builtin();
+
+        for (i, preset) in presets.iter().enumerate() {
+            let cells: usize = preset.size.iter().product();
+            assert!(
+                (preset.mines.count(cells) as usize) < cells,
+                "{}",
+                preset.name
+            );
+            assert!(presets[..i].iter().all(|p| p.name != preset.name));
+            assert!(!matches!(preset.mines, Mines::Count(_)), "{}", preset.name);
+        }
+
+        // the densities result in the usual numbers of mines at the preset sizes
+        let mines = |name: &str, cells: usize| {
+            let preset = presets.iter().find(|p| p.name == name).unwrap();
+            preset.mines.count(cells)
+        };
+        assert_eq!(mines("beginner 2D", 81), 10);
+        assert_eq!(mines("intermediate 2D", 256), 40);
+        assert_eq!(mines("expert 2D", 480), 99);
+        assert_eq!(mines("expert 6D", 1296), 100);
+
+        // and scale with the size of the board
+        assert_eq!(mines("beginner 2D", 2 * 81), 20);
+        assert_eq!(mines("expert 2D", 1000), 206);
+    }
+
+    #[test]
+    fn test_presets_in_config() {
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert!(config.presets.is_empty());
+
+        let config: Config = serde_json::from_str(
+            r#"{"presets": [{"name": "mine", "size": [5, 5, 2, 1, 1, 1], "mines": 8, "topology": "periodic"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.presets[0].cheats, 0);
+        assert_eq!(config.presets[0].topology.as_deref(), Some("periodic"));
+        assert_eq!(config.presets[0].neighbourhood, None);
+        assert_eq!(config.presets[0].mines, Mines::Count(8));
+
+        // densities are stored as strings
+        let config: Config = serde_json::from_str(
+            r#"{"presets": [{"name": "a", "size": [5, 5, 2, 1, 1, 1], "mines": "12.5%"}, {"name": "b", "size": [5, 5, 1, 1, 1, 1], "mines": "1/5"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.presets[0].mines, Mines::Percentage(12.5));
+        assert_eq!(config.presets[1].mines.count(25), 5);
+        assert!(serde_json::to_string(&config.presets[0])
+            .unwrap()
+            .contains(r#""mines":"12.5%""#));
+        assert!(serde_json::from_str::<Config>(
+            r#"{"presets": [{"name": "c", "size": [5, 5, 1, 1, 1, 1], "mines": "lots"}]}"#
+        )
+        .is_err());
+    }
+}
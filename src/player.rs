@@ -0,0 +1,152 @@
+use crate::board::{Action, Board, Observation, Rules, Uncovered};
+use std::fmt;
+
+#[cfg(test)]
+#[path = "player_tests.rs"]
+mod player_tests;
+
+type Cell = usize;
+
+/// A game is given up once the player has made this many actions per cell of the board.
+pub const MAX_ACTIONS_PER_CELL: u64 = 10;
+
+/// An automated player, it only gets to see what a human player would see.
+pub trait Player {
+    /// Called before each game.
+    fn start(&mut self, _observation: &Observation) {}
+
+    /// Returns the next action and the cell it is applied to, see `Observation::index`. None gives up the game.
+    fn play(&mut self, observation: &Observation) -> Option<(Action, Cell)>;
+}
+
+/// How a game played by a `Player` ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    Lost,
+    GaveUp,
+}
+
+/// The outcomes of a series of games.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub given_up: u32,
+    /// Number of actions in all games
+    pub actions: u64,
+}
+
+impl Report {
+    /// Returns the percentage of games that have been won.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.wins as f64 / self.games as f64 * 100.0
+        }
+    }
+
+    /// Counts a game.
+    pub fn add(&mut self, outcome: Outcome, actions: u64) {
+        self.games += 1;
+        self.actions += actions;
+        match outcome {
+            Outcome::Won => self.wins += 1,
+            Outcome::Lost => self.losses += 1,
+            Outcome::GaveUp => self.given_up += 1,
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} games, {} won ({:.1} %), {} lost, {} given up, {} actions",
+            self.games,
+            self.wins,
+            self.win_rate(),
+            self.losses,
+            self.given_up,
+            self.actions
+        )
+    }
+}
+
+/// Lets the player play the board until the game has been won or lost, or the player gives up.
+/// Returns the outcome and the number of actions. In practice mode uncovering a mine doesn't lose the game.
+pub fn play(player: &mut impl Player, board: &mut Board) -> (Outcome, u64) {
+    let max_actions = board.cell_count() as u64 * MAX_ACTIONS_PER_CELL;
+
+    player.start(&board.observe());
+
+    for actions in 1..=max_actions {
+        let (action, cell) = match player.play(&board.observe()) {
+            Some(action) => action,
+            None => return (Outcome::GaveUp, actions - 1),
+        };
+
+        if cell >= board.cell_count() {
+            // the player doesn't know the board
+            return (Outcome::GaveUp, actions);
+        }
+
+        let lost = match action {
+            Action::Uncover => board.uncover_cell(cell) == Uncovered::Lost,
+            Action::Chord => board.chord_cell(cell) == Uncovered::Lost,
+            Action::Flag => {
+                board.flag_cell(cell);
+                false
+            }
+            Action::Mark => {
+                board.mark_cell(cell);
+                false
+            }
+            Action::Cheat => {
+                board.cheat_cell(cell);
+                false
+            }
+        };
+
+        if lost {
+            return (Outcome::Lost, actions);
+        } else if board.is_won() {
+            return (Outcome::Won, actions);
+        }
+    }
+
+    (Outcome::GaveUp, max_actions)
+}
+
+/// Lets the player play a number of boards created with the given settings, see `Board::new`.
+/// The boards are generated from consecutive seeds starting at seed, so the results can be compared between players.
+///
+/// # Panics
+/// Panics if the settings are invalid, use `Board::validate` to check them.
+pub fn play_games(
+    player: &mut impl Player,
+    size: &[usize],
+    mines: u32,
+    cheats: u32,
+    rules: &Rules,
+    games: u32,
+    seed: u64,
+) -> Report {
+    let mut report = Report::default();
+
+    for i in 0..games {
+        let mut board = Board::new(
+            size,
+            mines,
+            cheats,
+            rules.clone(),
+            Some(seed.wrapping_add(i as u64)),
+        );
+        let (outcome, actions) = play(player, &mut board);
+        report.add(outcome, actions);
+    }
+
+    report
+}
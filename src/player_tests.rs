@@ -0,0 +1,92 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod player_tests {
+
+    use super::super::{play, play_games, Outcome, Player, Report};
+    use crate::board::{Action, CellState, Observation, Rules, Uncovered};
+    use crate::test_boards::one_mine;
+
+    type Cell = usize;
+
+    /// Uncovers the first covered cell.
+    struct FirstCovered;
+
+    impl Player for FirstCovered {
+        fn play(&mut self, observation: &Observation) -> Option<(Action, Cell)> {
+            observation
+                .cells()
+                .find(|(_, state)| *state == CellState::Covered)
+                .map(|(cell, _)| (Action::Uncover, cell))
+        }
+    }
+
+    /// Toggles the flag of a cell forever.
+    struct Indecisive;
+
+    impl Player for Indecisive {
+        fn play(&mut self, _observation: &Observation) -> Option<(Action, Cell)> {
+            Some((Action::Flag, 0))
+        }
+    }
+
+    #[test]
+    fn test_observation() {
+        let mut board = one_mine(0);
+        board.uncover_cell(0);
+        board.flag_cell(4);
+
+        let states: Vec<CellState> = board.observe().cells().map(|(_, s)| s).collect();
+        assert_eq!(
+            states,
+            [
+                CellState::Uncovered(0),
+                CellState::Uncovered(1),
+                CellState::Covered,
+                CellState::Covered,
+                CellState::Flagged
+            ]
+        );
+        assert_eq!(board.observe().mines_flagged(), 1);
+        assert_eq!(board.observe().exploded(), None);
+
+        // only the mine that lost the game is revealed
+        let mut practice = board.clone();
+        practice.rules.practice = true;
+        assert_eq!(practice.uncover_cell(2), Uncovered::Undone);
+        assert_eq!(practice.observe().exploded(), None);
+        assert_eq!(practice.undos, 1);
+
+        assert_eq!(board.uncover_cell(2), Uncovered::Lost);
+        assert_eq!(board.observe().cell(2), CellState::Exploded);
+        assert_eq!(board.observe().exploded(), Some(2));
+    }
+
+    #[test]
+    fn test_play() {
+        assert_eq!(
+            play(&mut FirstCovered, &mut one_mine(0)),
+            (Outcome::Lost, 2)
+        );
+        assert_eq!(
+            play(&mut Indecisive, &mut one_mine(0)),
+            (Outcome::GaveUp, 50)
+        );
+
+        // uncovering the mine doesn't lose the game in practice mode
+        let mut practice = one_mine(0);
+        practice.rules.practice = true;
+        assert_eq!(
+            play(&mut FirstCovered, &mut practice),
+            (Outcome::GaveUp, 50)
+        );
+        assert_eq!(practice.undos, 49);
+
+        // the first cell is always safe with the default rules
+        let report = play_games(&mut FirstCovered, &[4, 4], 1, 0, &Rules::default(), 20, 0);
+        assert_eq!(report.games, 20);
+        assert_eq!(report.wins + report.losses, 20);
+        assert!(report.wins > 0 && report.losses > 0, "{}", report);
+
+        assert_eq!(Report::default().win_rate(), 0.0);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::board::{Board, Rules};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, read_to_string, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+#[path = "replay_tests.rs"]
+mod replay_tests;
+
+/// A key pressed while playing, timestamped with the time spent playing in seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub time: f64,
+    pub key: char,
+}
+
+/// Used to (de)serialize a finished game, consisting of the mine layout and the keys pressed by the player.
+#[derive(Serialize, Deserialize)]
+pub struct Replay {
+    /// Size of the board as (x₁, x₂, …, xₙ)
+    pub size: Vec<usize>,
+    pub neighbourhood: String,
+    pub topology: String,
+    pub safe_start: String,
+    pub generator: String,
+    pub win_condition: String,
+    pub practice: bool,
+    pub safe_regions: String,
+    pub cheats_total: u32,
+    pub seed: u64,
+    pub solvable: Option<bool>,
+    /// The coordinates of the cells containing mines as (x₁, x₂, …, xₙ)
+    pub mines: Vec<Vec<usize>>,
+    /// "won" or "lost"
+    pub result: String,
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    /// Stores the mine layout of board together with the events leading to result.
+    pub fn new(board: &Board, result: &str, events: Vec<ReplayEvent>) -> Self {
+        Self {
+            size: board.size().to_vec(),
+            neighbourhood: board.rules.neighbourhood.to_string(),
+            topology: board.rules.topology.to_string(),
+            safe_start: board.rules.safe_start.to_string(),
+            generator: board.rules.generator.to_string(),
+            win_condition: board.rules.win_condition.to_string(),
+            practice: board.rules.practice,
+            safe_regions: board.rules.safe_regions.to_string(),
+            cheats_total: board.cheats_total,
+            seed: board.seed(),
+            solvable: board.solvable,
+            mines: board
+                .mine_cells()
+                .into_iter()
+                .map(|cell| board.coordinates(cell))
+                .collect(),
+            result: result.to_string(),
+            events,
+        }
+    }
+
+    /// Recreates the board as it was before the first event, returns an error if the replay is invalid.
+    pub fn to_board(&self) -> Result<Board, String> {
+        let rules = Rules {
+            neighbourhood: self.neighbourhood.parse()?,
+            topology: self.topology.parse()?,
+            safe_start: self.safe_start.parse()?,
+            generator: self.generator.parse()?,
+            win_condition: self.win_condition.parse()?,
+            practice: self.practice,
+            safe_regions: self.safe_regions.parse()?,
+        };
+        Board::validate(&self.size, self.mines.len() as u32, &rules)?;
+
+        let mut board = Board::new(
+            &self.size,
+            self.mines.len() as u32,
+            self.cheats_total,
+            rules,
+            Some(self.seed),
+        );
+
+        let mut mines = Vec::new();
+        for mine in &self.mines {
+            match board.index(mine) {
+                Some(cell) if mine.len() == self.size.len() => mines.push(cell),
+                _ => return Err("A mine is outside of the board".to_string()),
+            }
+        }
+        board.place_mines_at(&mines);
+        board.solvable = self.solvable;
+
+        Ok(board)
+    }
+
+    /// Returns the time of the last event.
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |e| e.time)
+    }
+
+    /// Reads a replay from a file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let string = read_to_string(path)
+            .map_err(|err| format!("Couldn't read {}: {}", path.display(), err))?;
+
+        serde_json::from_str(&string)
+            .map_err(|err| format!("Couldn't parse {}: {}", path.display(), err))
+    }
+
+    /// Writes the replay to a new file "name.json" in dir, creating dir if necessary. Existing replays are never
+    /// overwritten, "name-2.json", "name-3.json", … are used instead. Returns the path of the file.
+    pub fn write_new(&self, dir: &Path, name: &str) -> Result<PathBuf, String> {
+        let _ = create_dir_all(dir);
+        let string = serde_json::to_string(self).map_err(|err| err.to_string())?;
+
+        let mut path = dir.join(format!("{}.json", name));
+        for i in 2.. {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    return file
+                        .write_all(string.as_bytes())
+                        .map(|_| path.clone())
+                        .map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                    path = dir.join(format!("{}-{}.json", name, i));
+                }
+                Err(err) => return Err(format!("Couldn't write {}: {}", path.display(), err)),
+            }
+        }
+
+        Err(format!("Couldn't write {}", path.display()))
+    }
+}
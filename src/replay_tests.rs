@@ -0,0 +1,62 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod replay_tests {
+
+    use super::super::{Replay, ReplayEvent};
+    use crate::board::{Board, Boundary, CellState, Neighbourhood, Rules, Topology};
+
+    #[test]
+    fn test_write_new() {
+        let dir = std::env::temp_dir().join(format!("mines6d-replays-{}", std::process::id()));
+        let board = Board::new(&[3, 3], 1, 0, Rules::default(), Some(0));
+        let replay = Replay::new(&board, "lost", Vec::new());
+
+        // replays of games that ended in the same second don't overwrite each other
+        let first = replay.write_new(&dir, "100").unwrap();
+        let second = replay.write_new(&dir, "100").unwrap();
+        assert_eq!(first, dir.join("100.json"));
+        assert_eq!(second, dir.join("100-2.json"));
+        assert!(Replay::read(&second).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_to_board() {
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Moore,
+            topology: Topology::uniform(Boundary::Mirrored),
+            ..Rules::default()
+        };
+        let mut board = Board::new(&[5, 4, 3, 2], 20, 1, rules, Some(5));
+        let fresh = board.clone();
+        board.uncover_cell(board.index(&[1, 1, 1, 1]).unwrap());
+        board.flag_cell(0);
+
+        let events = vec![ReplayEvent {
+            time: 1.5,
+            key: ' ',
+        }];
+        let replay = Replay::new(&board, "lost", events.clone());
+        assert_eq!(replay.mines.len(), 20);
+        assert_eq!(replay.duration(), 1.5);
+
+        // the recreated board contains the same mines, but nothing has been uncovered
+        let restored = replay.to_board().unwrap();
+        assert!(restored.mines_placed);
+        assert_eq!(restored.mines_total, 20);
+        assert_eq!(restored.rules, fresh.rules);
+        assert!(restored
+            .observe()
+            .cells()
+            .all(|(_, state)| state == CellState::Covered));
+        assert_eq!(restored.mine_cells(), board.mine_cells());
+
+        let mut replay = replay;
+        replay.mines.push(vec![0, 0, 0, 2]);
+        assert!(replay.to_board().is_err());
+        replay.mines.pop();
+        replay.mines.push(vec![0, 0, 0]);
+        assert!(replay.to_board().is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{boardview::BoardView, replay::Replay};
+use cursive::{
+    event::{Event, EventResult},
+    view::View,
+    Printer, Vec2,
+};
+use std::{cmp::max, time::Instant};
+
+/// Number of lines used to display the state of the playback
+const STATUS_LINES: usize = 3;
+
+/// Plays back a replay by passing its events to a BoardView.
+pub struct ReplayView {
+    board_view: BoardView,
+    replay: Replay,
+    /// Index of the next event
+    next: usize,
+    /// Position of the playback in seconds
+    time: f64,
+    /// Factor by which the playback is faster than the recorded game
+    speed: f64,
+    paused: bool,
+    /// Time at which the playback position has been updated
+    last_update: Instant,
+}
+
+impl ReplayView {
+    pub fn new(board_view: BoardView, replay: Replay) -> Self {
+        Self {
+            board_view,
+            replay,
+            next: 0,
+            time: 0.0,
+            speed: 1.0,
+            paused: false,
+            last_update: Instant::now(),
+        }
+    }
+
+    /// Returns true if all events have been played.
+    fn is_finished(&self) -> bool {
+        self.next >= self.replay.events.len()
+    }
+
+    /// Passes the next event to the BoardView.
+    fn step(&mut self) {
+        if let Some(event) = self.replay.events.get(self.next) {
+            self.board_view.on_event(Event::Char(event.key));
+            self.time = self.time.max(event.time);
+            self.next += 1;
+        }
+    }
+
+    /// Advances the playback position by the time passed since the last update.
+    fn update(&mut self) {
+        if !self.paused {
+            self.time += self.last_update.elapsed().as_secs_f64() * self.speed;
+
+            while !self.is_finished() && self.replay.events[self.next].time <= self.time {
+                self.step();
+            }
+        }
+        self.last_update = Instant::now();
+    }
+
+    /// Returns the state of the playback as a formatted String.
+    fn format_status_string(&self) -> String {
+        let state = if self.is_finished() {
+            format!("finished ({})", self.replay.result)
+        } else if self.paused {
+            "paused".to_string()
+        } else {
+            "playing".to_string()
+        };
+
+        format!(
+            "Replay   {:.1} s / {:.1} s, speed {}x, {}",
+            self.time.min(self.replay.duration()),
+            self.replay.duration(),
+            self.speed,
+            state
+        )
+    }
+}
+
+impl View for ReplayView {
+    fn draw(&self, printer: &Printer) {
+        printer.print((0, 0), &self.format_status_string());
+        printer.print((0, 1), "Space pause/resume, n step, +/- speed");
+
+        self.board_view.draw(
+            &printer
+                .offset((0, STATUS_LINES))
+                .shrinked((0, STATUS_LINES)),
+        );
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.board_view
+            .layout(size.saturating_sub((0, STATUS_LINES)));
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let size = self
+            .board_view
+            .required_size(constraint.saturating_sub((0, STATUS_LINES)));
+
+        Vec2::new(
+            max(size.x, self.format_status_string().len()),
+            size.y + STATUS_LINES,
+        )
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Refresh => self.update(),
+            Event::Char(' ') => {
+                self.update();
+                self.paused = !self.paused;
+            }
+            Event::Char('n') => {
+                self.paused = true;
+                self.step();
+            }
+            Event::Char('+') => self.speed = (self.speed * 2.0).min(64.0),
+            Event::Char('-') => self.speed = (self.speed / 2.0).max(0.125),
+            _ => return EventResult::Ignored,
+        }
+
+        EventResult::Consumed(None)
+    }
+}
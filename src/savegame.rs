@@ -0,0 +1,158 @@
+use crate::{
+    board::{Board, Command, Rules},
+    replay::ReplayEvent,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    path::Path,
+};
+
+#[cfg(test)]
+#[path = "savegame_tests.rs"]
+mod savegame_tests;
+
+/// Used to (de)serialize the state of a running game, consisting of the mine layout and the commands executed so far.
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    /// Size of the board as (x₁, x₂, …, xₙ)
+    pub size: Vec<usize>,
+    pub neighbourhood: String,
+    pub topology: String,
+    pub safe_start: String,
+    pub generator: String,
+    pub win_condition: String,
+    pub practice: bool,
+    pub safe_regions: String,
+    pub mines_total: u32,
+    pub cheats_total: u32,
+    pub seed: u64,
+    pub solvable: Option<bool>,
+    pub mines_placed: bool,
+    /// The coordinates of the cells containing mines as (x₁, x₂, …, xₙ)
+    pub mines: Vec<Vec<usize>>,
+    /// The commands executed so far, see `Board::commands`
+    pub commands: Vec<Command>,
+    /// Coordinates of the cursor as (x₁, x₂, …, xₙ)
+    pub cursor: Vec<usize>,
+    /// Time spent playing in seconds
+    pub elapsed: f64,
+    /// The keys pressed so far, used to write the replay
+    pub replay_events: Vec<ReplayEvent>,
+}
+
+impl SaveGame {
+    /// Stores the mine layout and the commands of board, the cursor position and the events recorded for the
+    /// replay. The cursor is given as (x₁, x₂, …, xₙ).
+    pub fn new(
+        board: &Board,
+        cursor: &[usize],
+        elapsed: f64,
+        replay_events: Vec<ReplayEvent>,
+    ) -> Self {
+        Self {
+            size: board.size().to_vec(),
+            neighbourhood: board.rules.neighbourhood.to_string(),
+            topology: board.rules.topology.to_string(),
+            safe_start: board.rules.safe_start.to_string(),
+            generator: board.rules.generator.to_string(),
+            win_condition: board.rules.win_condition.to_string(),
+            practice: board.rules.practice,
+            safe_regions: board.rules.safe_regions.to_string(),
+            mines_total: board.mines_total,
+            cheats_total: board.cheats_total,
+            seed: board.seed(),
+            solvable: board.solvable,
+            mines_placed: board.mines_placed,
+            mines: board
+                .mine_cells()
+                .into_iter()
+                .map(|cell| board.coordinates(cell))
+                .collect(),
+            commands: board.commands.clone(),
+            cursor: cursor.to_vec(),
+            elapsed,
+            replay_events,
+        }
+    }
+
+    /// Restores the board by executing the saved commands again, returns an error if the saved state is invalid.
+    pub fn to_board(&self) -> Result<Board, String> {
+        let rules = Rules {
+            neighbourhood: self.neighbourhood.parse()?,
+            topology: self.topology.parse()?,
+            safe_start: self.safe_start.parse()?,
+            generator: self.generator.parse()?,
+            win_condition: self.win_condition.parse()?,
+            practice: self.practice,
+            safe_regions: self.safe_regions.parse()?,
+        };
+        Board::validate(&self.size, self.mines_total, &rules)?;
+
+        let mut board = Board::new(
+            &self.size,
+            self.mines_total,
+            self.cheats_total,
+            rules,
+            Some(self.seed),
+        );
+
+        if self.cursor.len() != self.size.len() || board.index(&self.cursor).is_none() {
+            return Err("The cursor is outside of the board".to_string());
+        }
+
+        if self.mines_placed {
+            let mut mines = Vec::new();
+            for mine in &self.mines {
+                match board.index(mine) {
+                    Some(cell) if mine.len() == self.size.len() => mines.push(cell),
+                    _ => return Err("A mine is outside of the board".to_string()),
+                }
+            }
+            board.place_mines_at(&mines);
+            if board.mines_total != self.mines_total {
+                return Err("The number of mines doesn't match".to_string());
+            }
+        }
+        board.solvable = self.solvable;
+
+        for command in &self.commands {
+            if command
+                .cell()
+                .is_some_and(|cell| cell >= board.cell_count())
+            {
+                return Err("A command is outside of the board".to_string());
+            }
+            board.execute(*command);
+        }
+        if board.exploded.is_some() {
+            return Err("The saved game has already been lost".to_string());
+        }
+
+        Ok(board)
+    }
+
+    /// Reads a saved game from a file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let string = read_to_string(path)
+            .map_err(|err| format!("Couldn't read {}: {}", path.display(), err))?;
+
+        serde_json::from_str(&string)
+            .map_err(|err| format!("Couldn't parse {}: {}", path.display(), err))
+    }
+
+    /// Writes the saved game to a file, creating the parent directories if necessary.
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            let _ = create_dir_all(parent);
+        }
+
+        let string = serde_json::to_string(self).map_err(|err| err.to_string())?;
+        write(path, string).map_err(|err| format!("Couldn't write {}: {}", path.display(), err))
+    }
+
+    /// Removes the saved game, e.g. after the game has ended.
+    pub fn remove(path: &Path) {
+        let _ = remove_file(path);
+    }
+}
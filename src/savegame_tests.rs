@@ -0,0 +1,71 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod savegame_tests {
+
+    use super::super::SaveGame;
+    use crate::board::{Board, Boundary, Command, Neighbourhood, Rules, Topology};
+
+    #[test]
+    fn test_round_trip() {
+        let rules = Rules {
+            neighbourhood: Neighbourhood::Moore,
+            topology: Topology::uniform(Boundary::Periodic),
+            practice: true,
+            ..Rules::default()
+        };
+        let mut board = Board::new(&[5, 5, 2], 6, 2, rules, Some(7));
+        board.uncover_cell(board.index(&[2, 2, 0]).unwrap());
+        board.flag_cell(board.index(&[4, 4, 1]).unwrap());
+        board.mark_cell(board.index(&[0, 0, 1]).unwrap());
+        board.undo();
+
+        let save = SaveGame::new(&board, &[4, 4, 1], 12.5, Vec::new());
+        let string = serde_json::to_string(&save).unwrap();
+        let restored: SaveGame = serde_json::from_str(&string).unwrap();
+        let restored_board = restored.to_board().unwrap();
+
+        assert!(restored_board.observe().cells().eq(board.observe().cells()));
+        assert_eq!(restored_board.mine_cells(), board.mine_cells());
+        assert_eq!(restored_board.rules, board.rules);
+        assert_eq!(restored_board.mines_total, board.mines_total);
+        assert_eq!(restored_board.mines_flagged, board.mines_flagged);
+        assert_eq!(restored_board.cheats_remaining, board.cheats_remaining);
+        assert_eq!(restored_board.mines_placed, board.mines_placed);
+        assert_eq!(restored_board.seed(), board.seed());
+        assert_eq!(restored_board.moves(), board.moves());
+        assert_eq!(restored_board.undos, 1);
+        assert_eq!(restored_board.move_counts, board.move_counts);
+        assert_eq!(restored.size, [5, 5, 2]);
+        assert_eq!(restored.cursor, [4, 4, 1]);
+        assert_eq!(restored.elapsed, 12.5);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut board = Board::new(&[3, 3], 2, 0, Rules::default(), Some(0));
+        board.uncover_cell(4);
+
+        let mut save = SaveGame::new(&board, &[1, 1], 0.0, Vec::new());
+        assert!(save.to_board().is_ok());
+        save.mines.pop();
+        assert!(save.to_board().is_err());
+        save.mines.push(vec![3, 0]);
+        assert!(save.to_board().is_err());
+
+        let mut save = SaveGame::new(&board, &[1, 3], 0.0, Vec::new());
+        assert!(save.to_board().is_err());
+        save.cursor = vec![1, 1];
+        save.neighbourhood = "hexagonal".to_string();
+        assert!(save.to_board().is_err());
+
+        // commands outside of the board
+        let mut save = SaveGame::new(&board, &[1, 1], 0.0, Vec::new());
+        save.commands.push(Command::Flag(9));
+        assert!(save.to_board().is_err());
+
+        // a saved game that has already been lost
+        let mine = board.mine_cells()[0];
+        save.commands = vec![Command::Uncover(mine)];
+        assert!(save.to_board().is_err());
+    }
+}
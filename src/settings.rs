@@ -0,0 +1,356 @@
+use crate::{
+    board::{
+        Board, Generator, Neighbourhood, Rules, SafeRegions, SafeStart, Topology, WinCondition,
+        MAX_DIMENSIONS,
+    },
+    options::Config,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+#[cfg(test)]
+#[path = "settings_tests.rs"]
+mod settings_tests;
+
+/// The number of mines, either as a count or relative to the number of cells of the board.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mines {
+    Count(u32),
+    /// Percentage of the cells that contain a mine, e.g. "12.5%"
+    Percentage(f64),
+    /// One mine per the given number of cells, e.g. "1/8"
+    CellsPerMine(f64),
+}
+
+impl Mines {
+    /// Returns the number of mines on a board having the given number of cells, densities are rounded.
+    pub fn count(&self, cells: usize) -> u32 {
+        match self {
+            Self::Count(mines) => *mines,
+            Self::Percentage(p) => (cells as f64 * p / 100.0).round() as u32,
+            Self::CellsPerMine(n) => (cells as f64 / n).round() as u32,
+        }
+    }
+}
+
+impl fmt::Display for Mines {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Count(mines) => write!(f, "{}", mines),
+            Self::Percentage(p) => write!(f, "{}%", p),
+            Self::CellsPerMine(n) => write!(f, "1/{}", n),
+        }
+    }
+}
+
+impl FromStr for Mines {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(p) = s.strip_suffix('%') {
+            match p.trim().parse::<f64>() {
+                Ok(p) if (0.0..100.0).contains(&p) => Ok(Self::Percentage(p)),
+                Ok(_) => Err("Mines: the percentage must be at least 0 and below 100".to_string()),
+                Err(_) => Err(format!("Mines: \"{}\" is not a percentage", s)),
+            }
+        } else if let Some(n) = s.strip_prefix("1/") {
+            match n.trim().parse::<f64>() {
+                Ok(n) if n >= 1.0 && n.is_finite() => Ok(Self::CellsPerMine(n)),
+                Ok(_) => Err("Mines: there must be at least one cell per mine".to_string()),
+                Err(_) => Err(format!(
+                    "Mines: \"{}\" is not a number of cells per mine",
+                    s
+                )),
+            }
+        } else if s.is_empty() || s.chars().all(|c| c.is_ascii_digit()) {
+            parse_number("Mines", s).map(Self::Count)
+        } else {
+            Err(format!(
+                "Mines: \"{}\" is neither a number, a percentage like \"12%\" nor a ratio like \"1/8\"",
+                s
+            ))
+        }
+    }
+}
+
+// counts are stored as numbers, densities as strings
+impl Serialize for Mines {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Count(mines) => serializer.serialize_u32(*mines),
+            _ => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Mines {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Count(u32),
+            Text(String),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Count(mines) => Ok(Self::Count(mines)),
+            Value::Text(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// The settings of a new game as entered in the main menu.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Input {
+    /// Sizes of (x₁, x₂, …, xₙ), one per dimension
+    pub size: Vec<String>,
+    /// A count or a density, see `Mines`
+    pub mines: String,
+    pub cheats: String,
+    pub neighbourhood: String,
+    pub topology: String,
+    pub safe_start: String,
+    pub generator: String,
+    pub win_condition: String,
+    /// Boxes of cells that never contain mines, see `SafeRegions`
+    pub safe_regions: String,
+    /// An empty seed results in a random board
+    pub seed: String,
+    pub practice: bool,
+}
+
+/// The validated settings of a new game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// Size of the board as (x₁, x₂, …, xₙ)
+    pub size: Vec<usize>,
+    pub mines: u32,
+    pub cheats: u32,
+    pub rules: Rules,
+    pub seed: Option<u64>,
+}
+
+impl Input {
+    /// Returns the default settings, the rules are taken from the config file if there is one.
+    pub fn from_config(config: Option<&Config>) -> Self {
+        let mut input = Self {
+            size: ["10", "10", "3"].map(|x| x.to_string()).to_vec(),
+            mines: "15".to_string(),
+            cheats: "0".to_string(),
+            neighbourhood: Neighbourhood::default().to_string(),
+            topology: Topology::default().to_string(),
+            safe_start: SafeStart::default().to_string(),
+            generator: Generator::default().to_string(),
+            win_condition: WinCondition::default().to_string(),
+            safe_regions: SafeRegions::default().to_string(),
+            seed: "".to_string(),
+            practice: false,
+        };
+
+        if let Some(config) = config {
+            input.neighbourhood = config.neighbourhood.clone();
+            input.topology = config.topology.clone();
+            input.safe_start = config.safe_start.clone();
+            input.generator = config.generator.clone();
+            input.win_condition = config.win_condition.clone();
+            input.practice = config.practice;
+        }
+
+        input
+    }
+
+    /// Sets the sizes from a list like "10x10x3" or "10,10,3" starting at x₁, the board has one dimension per size.
+    pub fn set_size(&mut self, size: &str) -> Result<(), String> {
+        let sizes: Vec<&str> = size.split(['x', '×', ',']).collect();
+        if sizes.len() > MAX_DIMENSIONS {
+            return Err(format!(
+                "The size \"{}\" has more than {} axes",
+                size, MAX_DIMENSIONS
+            ));
+        }
+
+        self.size = sizes.iter().map(|x| x.trim().to_string()).collect();
+        Ok(())
+    }
+
+    /// Changes the number of dimensions, added axes have size 1.
+    pub fn set_dimensions(&mut self, dimensions: usize) {
+        self.size.resize(dimensions, "1".to_string());
+    }
+
+    /// Parses and validates the settings, returns one error message per invalid field otherwise.
+    /// Checks that need several fields, like the number of mines fitting on the board, are only done once
+    /// the fields themselves are valid.
+    pub fn parse(&self) -> Result<Settings, Vec<String>> {
+        let mut errors = Vec::new();
+
+        let size = self.parse_size().map_err(|mut e| errors.append(&mut e));
+        let mines = self.mines.parse::<Mines>().map_err(|e| errors.push(e));
+        let cheats = parse_number::<u32>("Cheats", &self.cheats).map_err(|e| errors.push(e));
+        let neighbourhood = self.neighbourhood.parse().map_err(|e| errors.push(e));
+        let topology = self.topology.parse().map_err(|e| errors.push(e));
+        let safe_start = self.safe_start.parse().map_err(|e| errors.push(e));
+        let generator = self.generator.parse().map_err(|e| errors.push(e));
+        let win_condition = self.win_condition.parse().map_err(|e| errors.push(e));
+        let safe_regions = self.safe_regions.parse().map_err(|e| errors.push(e));
+        let seed = self.parse_seed().map_err(|e| errors.push(e));
+
+        let settings = match (
+            size,
+            mines,
+            cheats,
+            neighbourhood,
+            topology,
+            safe_start,
+            generator,
+            win_condition,
+            safe_regions,
+            seed,
+        ) {
+            (
+                Ok(size),
+                Ok(mines),
+                Ok(cheats),
+                Ok(neighbourhood),
+                Ok(topology),
+                Ok(safe_start),
+                Ok(generator),
+                Ok(win_condition),
+                Ok(safe_regions),
+                Ok(seed),
+            ) => Settings {
+                mines: mines.count(Board::cells(&size).map_err(|e| vec![e])?),
+                size,
+                cheats,
+                rules: Rules {
+                    neighbourhood,
+                    topology,
+                    safe_start,
+                    generator,
+                    win_condition,
+                    practice: self.practice,
+                    safe_regions,
+                },
+                seed,
+            },
+            _ => return Err(errors),
+        };
+
+        Board::validate(&settings.size, settings.mines, &settings.rules).map_err(|e| vec![e])?;
+        Ok(settings)
+    }
+
+    /// Describes the board while the settings are being entered: the number of cells and the density of the
+    /// mines as far as they can be determined, followed by the errors.
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+
+        let cells = self
+            .parse_size()
+            .ok()
+            .and_then(|size| Board::cells(&size).ok());
+        let mines = self.mines.parse::<Mines>().ok();
+
+        match (cells, mines) {
+            (Some(cells), Some(mines)) => lines.push(format!(
+                "{} cells, {}",
+                cells,
+                describe_density(cells, mines.count(cells))
+            )),
+            (Some(cells), None) => lines.push(format!("{} cells", cells)),
+            _ => (),
+        }
+
+        match self.parse() {
+            Ok(settings) => lines.push(format!(
+                "up to {} neighbours",
+                settings.rules.neighbourhood.max_neighbors(&settings.size)
+            )),
+            Err(errors) => lines.extend(errors),
+        }
+
+        lines.join("\n")
+    }
+
+    // parses the sizes of the axes
+    fn parse_size(&self) -> Result<Vec<usize>, Vec<String>> {
+        let mut size = Vec::new();
+        let mut errors = Vec::new();
+
+        for (i, value) in self.size.iter().enumerate() {
+            let axis = format!("Size of {}", axis_name(i));
+            match parse_number::<usize>(&axis, value) {
+                Ok(0) => errors.push(format!("{} must be at least 1", axis)),
+                Ok(x) => size.push(x),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(size)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn parse_seed(&self) -> Result<Option<u64>, String> {
+        if self.seed.trim().is_empty() {
+            Ok(None)
+        } else {
+            self.seed
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("Invalid seed \"{}\"", self.seed))
+        }
+    }
+}
+
+impl Settings {
+    pub fn cells(&self) -> usize {
+        self.size.iter().product()
+    }
+}
+
+/// Describes the number of mines relative to the number of cells, e.g. "15 mines, 5.000 % (20.0 cells per mine)".
+pub fn describe_density(cells: usize, mines: u32) -> String {
+    if mines == 0 {
+        return "no mines".to_string();
+    }
+
+    format!(
+        "{} mines, {:.3} % ({:.1} cells per mine)",
+        mines,
+        mines as f64 / cells as f64 * 100.0,
+        cells as f64 / mines as f64
+    )
+}
+
+// parses a non-negative integer, the error names the field
+fn parse_number<T: std::str::FromStr>(field: &str, value: &str) -> Result<T, String> {
+    if value.trim().is_empty() {
+        return Err(format!("{} is empty", field));
+    }
+
+    value.trim().parse().map_err(|_| {
+        if value.trim().chars().all(|c| c.is_ascii_digit()) {
+            format!("{}: {} is too large", field, value.trim())
+        } else {
+            format!("{}: \"{}\" is not a whole number", field, value.trim())
+        }
+    })
+}
+
+/// Returns the name of an axis, 0 being "x₁".
+pub fn axis_name(axis: usize) -> String {
+    let subscript: String = (axis + 1)
+        .to_string()
+        .chars()
+        .map(|digit| {
+            ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉'][digit as usize - '0' as usize]
+        })
+        .collect();
+    format!("x{}", subscript)
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod settings_tests {
+
+    use super::super::{describe_density, Input, Mines};
+    use crate::board::{Neighbourhood, MAX_CELLS};
+    use crate::options::Config;
+
+    fn input(size: &[&str], mines: &str) -> Input {
+        Input {
+            size: size.iter().map(|x| x.to_string()).collect(),
+            mines: mines.to_string(),
+            cheats: "0".to_string(),
+            neighbourhood: "von-neumann".to_string(),
+            topology: "bounded".to_string(),
+            safe_start: "cell".to_string(),
+            generator: "random".to_string(),
+            win_condition: "any".to_string(),
+            safe_regions: "".to_string(),
+            seed: "".to_string(),
+            practice: false,
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let settings = input(&["10", "8", " 3 "], "15").parse().unwrap();
+        assert_eq!(settings.size, [10, 8, 3]);
+        assert_eq!(settings.cells(), 240);
+        assert_eq!(settings.mines, 15);
+        assert_eq!(settings.seed, None);
+        assert_eq!(settings.rules.neighbourhood, Neighbourhood::VonNeumann);
+
+        // no mines at all is a valid, if boring, game
+        assert!(input(&["10", "10"], "0").parse().is_ok());
+    }
+
+    #[test]
+    fn test_errors() {
+        // every invalid field is reported
+        let mut i = input(&["10", "0", "x"], "-1");
+        i.seed = "abc".to_string();
+        let errors = i.parse().unwrap_err();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors[0].contains("x₂"));
+        assert!(errors[1].contains("x₃"));
+
+        // at least one cell must be free of mines
+        let errors = input(&["3", "3"], "9").parse().unwrap_err();
+        assert!(errors[0].contains("at most 8"), "{:?}", errors);
+        assert!(input(&["3", "3"], "8").parse().is_ok());
+
+        // the number of cells overflows
+        let huge = usize::MAX.to_string();
+        let errors = input(&[&huge, "2"], "1").parse().unwrap_err();
+        assert!(errors[0].contains(&MAX_CELLS.to_string()), "{:?}", errors);
+
+        // too many neighbours
+        let mut i = input(&["9", "9", "9", "9", "9", "9"], "10");
+        i.neighbourhood = "chebyshev:4".to_string();
+        assert!(i.parse().unwrap_err()[0].contains("neighbourhood"));
+    }
+
+    #[test]
+    fn test_summary() {
+        let summary = input(&["10", "10"], "20").summary();
+        assert!(summary.starts_with("100 cells, 20 mines, 20.000 % (5.0 cells per mine)"));
+
+        let summary = input(&["10", "10"], "").summary();
+        assert_eq!(summary, "100 cells\nMines is empty");
+
+        assert_eq!(describe_density(100, 0), "no mines");
+    }
+
+    #[test]
+    fn test_density() {
+        assert_eq!("15".parse(), Ok(Mines::Count(15)));
+        assert_eq!(" 12.5 % ".parse(), Ok(Mines::Percentage(12.5)));
+        assert_eq!("1/8".parse(), Ok(Mines::CellsPerMine(8.0)));
+        assert!("100%".parse::<Mines>().is_err());
+        assert!("1/0.5".parse::<Mines>().is_err());
+        assert!("many".parse::<Mines>().is_err());
+
+        for mines in ["15", "12.5%", "1/8"] {
+            assert_eq!(mines.parse::<Mines>().unwrap().to_string(), mines);
+        }
+
+        // the density is converted for the entered size
+        let settings = input(&["10", "10", "3"], "10%").parse().unwrap();
+        assert_eq!(settings.mines, 30);
+        let settings = input(&["9", "9"], "1/8").parse().unwrap();
+        assert_eq!(settings.mines, 10);
+        assert!(input(&["3", "3"], "99%").parse().is_err());
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut i = Input::from_config(None);
+        assert_eq!(i.parse().unwrap().size, [10, 10, 3]);
+
+        i.set_size("5x4×3,2").unwrap();
+        assert_eq!(i.size, ["5", "4", "3", "2"]);
+        assert_eq!(i.parse().unwrap().size, [5, 4, 3, 2]);
+        assert!(i.set_size("1x1x1x1x1x1x1x1x1x1").is_ok());
+        assert!(i.set_size("1x1x1x1x1x1x1x1x1x1x1").is_err());
+
+        // changing the number of dimensions keeps the leading axes
+        i.set_size("5x4").unwrap();
+        i.set_dimensions(3);
+        assert_eq!(i.size, ["5", "4", "1"]);
+        i.set_dimensions(1);
+        i.mines = "2".to_string();
+        assert_eq!(i.parse().unwrap().size, [5]);
+
+        let config: Config = serde_json::from_str(r#"{"topology": "periodic"}"#).unwrap();
+        let i = Input::from_config(Some(&config));
+        assert_eq!(i.topology, "periodic");
+        assert_eq!(i.safe_start, "cell");
+    }
+}
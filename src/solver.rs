@@ -0,0 +1,870 @@
+use crate::board::{Board, CellState, Observation, Uncovered};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(test)]
+#[path = "solver_tests.rs"]
+mod solver_tests;
+
+type Cell = usize;
+
+/// A cell whose content follows from the uncovered cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deduction {
+    pub cell: Cell,
+    pub mine: bool,
+    /// The uncovered cells whose numbers imply the deduction
+    pub reason: Vec<Cell>,
+}
+
+/// The number of mines in a set of covered cells, derived from an uncovered cell.
+#[derive(Clone, Debug)]
+struct Constraint {
+    /// The uncovered cells this constraint is derived from
+    sources: Vec<Cell>,
+    /// The covered cells and how often they are counted (cells beyond a mirrored edge are counted twice)
+    cells: BTreeMap<Cell, i32>,
+    /// The number of mines in cells
+    mines: i32,
+}
+
+impl Constraint {
+    /// Returns the deductions that follow from this constraint alone.
+    fn deduce(&self) -> Vec<Deduction> {
+        let total: i32 = self.cells.values().sum();
+
+        let mine = if self.mines == 0 {
+            false
+        } else if self.mines == total {
+            true
+        } else {
+            return Vec::new();
+        };
+
+        self.cells
+            .keys()
+            .map(|cell| Deduction {
+                cell: *cell,
+                mine,
+                reason: self.sources.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns self - other if the cells of other are a subset of the cells of self.
+    fn difference(&self, other: &Self) -> Option<Self> {
+        let mut cells = self.cells.clone();
+
+        for (cell, weight) in &other.cells {
+            match cells.get_mut(cell) {
+                Some(w) if *w >= *weight => *w -= weight,
+                _ => return None,
+            }
+        }
+        cells.retain(|_, w| *w > 0);
+
+        let mut sources = self.sources.clone();
+        sources.extend(&other.sources);
+
+        Some(Self {
+            sources,
+            cells,
+            mines: self.mines - other.mines,
+        })
+    }
+}
+
+/// The number of mines left in the covered cells that aren't known to be mines, given by the total number of mines.
+struct MineCount<'a> {
+    cells: &'a BTreeSet<Cell>,
+    mines: i32,
+}
+
+/// Returns the constraints given by the numbers of the uncovered cells, cells in known_mines are treated as mines.
+/// Flags are ignored, because they might be wrong.
+fn constraints(observation: &Observation, known_mines: &BTreeSet<Cell>) -> Vec<Constraint> {
+    let mut result = Vec::new();
+
+    for (cell, state) in observation.cells() {
+        let mines = match state {
+            CellState::Uncovered(mines) => mines,
+            _ => continue,
+        };
+
+        let mut constraint = Constraint {
+            sources: vec![cell],
+            cells: BTreeMap::new(),
+            mines: mines as i32,
+        };
+
+        for n in observation.neighbors(cell) {
+            if known_mines.contains(&n) {
+                constraint.mines -= 1;
+            } else if !matches!(observation.cell(n), CellState::Uncovered(_)) {
+                *constraint.cells.entry(n).or_insert(0) += 1;
+            }
+        }
+
+        if !constraint.cells.is_empty() {
+            result.push(constraint);
+        }
+    }
+
+    result
+}
+
+/// The solutions of a set of constraints that share cells, i.e. a connected part of the frontier.
+#[derive(Clone, Debug)]
+struct Component {
+    /// The covered cells of the component
+    cells: Vec<Cell>,
+    /// The uncovered cells the constraints are derived from
+    sources: Vec<Cell>,
+    /// Maps the number of mines in a solution to the number of such solutions
+    /// and the number of those solutions in which each of the cells is a mine
+    solutions: BTreeMap<usize, (f64, Vec<f64>)>,
+    /// False if the solutions have been sampled
+    exact: bool,
+}
+
+/// The maximum number of steps used to enumerate the solutions of a single component.
+const MAX_STEPS: usize = 100_000;
+
+/// Splits the constraints into groups that share cells.
+fn components(constraints: &[Constraint]) -> Vec<Vec<usize>> {
+    let mut by_cell: BTreeMap<Cell, Vec<usize>> = BTreeMap::new();
+    for (i, c) in constraints.iter().enumerate() {
+        for cell in c.cells.keys() {
+            by_cell.entry(*cell).or_default().push(i);
+        }
+    }
+
+    let mut visited = vec![false; constraints.len()];
+    let mut result = Vec::new();
+
+    for start in 0..constraints.len() {
+        if visited[start] {
+            continue;
+        }
+
+        visited[start] = true;
+        let mut component = vec![start];
+        let mut i = 0;
+
+        while i < component.len() {
+            for cell in constraints[component[i]].cells.keys() {
+                for j in &by_cell[cell] {
+                    if !visited[*j] {
+                        visited[*j] = true;
+                        component.push(*j);
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        result.push(component);
+    }
+
+    result
+}
+
+/// Enumerates all solutions of the given constraints, returns None if this takes more than `MAX_STEPS` steps.
+fn solve_component(constraints: &[&Constraint]) -> Option<Component> {
+    let (mut search, cells) = Search::new(constraints);
+
+    search.run(0, 0);
+    if search.steps > MAX_STEPS {
+        return None;
+    }
+
+    Some(search.into_component(constraints, cells, true))
+}
+
+/// Estimates the solutions of the given constraints using randomized searches that each find a single solution.
+/// The solutions are not sampled exactly uniformly, returns None if no solution has been found.
+fn sample_component(constraints: &[&Constraint], rng: &mut ChaCha8Rng) -> Option<Component> {
+    let (mut search, cells) = Search::new(constraints);
+    search.max_solutions = 1;
+    // a search needs one step per cell to find a solution, plus some room for backtracking
+    search.max_steps = (MAX_STEPS / SAMPLES).max(2 * cells.len());
+
+    for _ in 0..SAMPLES {
+        search.rng = Some(ChaCha8Rng::seed_from_u64(rng.gen()));
+        search.steps = 0;
+        search.found = 0;
+        search.run(0, 0);
+    }
+
+    if search.solutions.is_empty() {
+        None
+    } else {
+        Some(search.into_component(constraints, cells, false))
+    }
+}
+
+/// The number of solutions sampled by `sample_component`.
+const SAMPLES: usize = 200;
+
+/// The state of the backtracking search used by `solve_component` and `sample_component`.
+struct Search {
+    uses: Vec<Vec<(usize, i32)>>,
+    /// The number of mines that still have to be placed for each constraint
+    mines: Vec<i32>,
+    /// The total weight of the unassigned cells for each constraint
+    remaining: Vec<i32>,
+    assignment: Vec<bool>,
+    solutions: BTreeMap<usize, (f64, Vec<f64>)>,
+    steps: usize,
+    /// The search stops after this many steps
+    max_steps: usize,
+    /// The number of solutions found since the search was started
+    found: usize,
+    /// The search stops after finding this many solutions
+    max_solutions: usize,
+    /// Used to randomize the order in which cells are assigned mines
+    rng: Option<ChaCha8Rng>,
+}
+
+impl Search {
+    /// Creates a search for the given constraints and returns it together with the cells to assign.
+    fn new(constraints: &[&Constraint]) -> (Self, Vec<Cell>) {
+        // the cells in the order in which they are first used by the constraints, this keeps related cells close
+        let mut cells: Vec<Cell> = Vec::new();
+        let mut index: BTreeMap<Cell, usize> = BTreeMap::new();
+        for c in constraints {
+            for cell in c.cells.keys() {
+                if !index.contains_key(cell) {
+                    index.insert(*cell, cells.len());
+                    cells.push(*cell);
+                }
+            }
+        }
+
+        // the constraints that use each cell, and the weight of the cell in the constraint
+        let mut uses: Vec<Vec<(usize, i32)>> = vec![Vec::new(); cells.len()];
+        for (i, c) in constraints.iter().enumerate() {
+            for (cell, weight) in &c.cells {
+                uses[index[cell]].push((i, *weight));
+            }
+        }
+
+        let search = Self {
+            uses,
+            mines: constraints.iter().map(|c| c.mines).collect(),
+            remaining: constraints.iter().map(|c| c.cells.values().sum()).collect(),
+            assignment: vec![false; cells.len()],
+            solutions: BTreeMap::new(),
+            steps: 0,
+            max_steps: MAX_STEPS,
+            found: 0,
+            max_solutions: usize::MAX,
+            rng: None,
+        };
+
+        (search, cells)
+    }
+
+    /// Converts the solutions found by the search into a `Component`.
+    fn into_component(
+        self,
+        constraints: &[&Constraint],
+        cells: Vec<Cell>,
+        exact: bool,
+    ) -> Component {
+        Component {
+            cells,
+            sources: constraints
+                .iter()
+                .flat_map(|c| c.sources.iter().copied())
+                .collect(),
+            solutions: self.solutions,
+            exact,
+        }
+    }
+
+    /// Assigns the cells starting at cell, returns false if the search should stop.
+    fn run(&mut self, cell: usize, mines: usize) -> bool {
+        self.steps += 1;
+        if self.steps > self.max_steps {
+            return false;
+        }
+
+        if cell == self.assignment.len() {
+            let cells = self.assignment.len();
+            let entry = self
+                .solutions
+                .entry(mines)
+                .or_insert_with(|| (0.0, vec![0.0; cells]));
+
+            entry.0 += 1.0;
+            for (i, mine) in self.assignment.iter().enumerate() {
+                if *mine {
+                    entry.1[i] += 1.0;
+                }
+            }
+
+            self.found += 1;
+            return self.found < self.max_solutions;
+        }
+
+        let mut order = [false, true];
+        if let Some(rng) = &mut self.rng {
+            if rng.gen() {
+                order.reverse();
+            }
+        }
+
+        for mine in order {
+            self.assignment[cell] = mine;
+
+            let mut valid = true;
+            for (c, weight) in &self.uses[cell] {
+                self.remaining[*c] -= weight;
+                if mine {
+                    self.mines[*c] -= weight;
+                }
+                if self.mines[*c] < 0 || self.mines[*c] > self.remaining[*c] {
+                    valid = false;
+                }
+            }
+
+            let completed = !valid || self.run(cell + 1, mines + mine as usize);
+
+            for (c, weight) in &self.uses[cell] {
+                self.remaining[*c] += weight;
+                if mine {
+                    self.mines[*c] += weight;
+                }
+            }
+
+            if !completed {
+                return false;
+            }
+        }
+
+        self.assignment[cell] = false;
+        true
+    }
+}
+
+/// The probability of each covered cell containing a mine.
+#[derive(Clone, Debug)]
+pub struct Probabilities {
+    /// The covered cells next to uncovered cells
+    pub frontier: BTreeMap<Cell, f64>,
+    /// The probability for all other covered cells
+    pub interior: f64,
+    /// The covered cells next to uncovered cells whose probability couldn't be estimated
+    pub unknown: BTreeSet<Cell>,
+    /// False if some of the probabilities have been estimated
+    pub exact: bool,
+}
+
+impl Probabilities {
+    /// Returns the probability of a covered cell containing a mine, None if it is unknown.
+    pub fn get(&self, cell: Cell) -> Option<f64> {
+        if self.unknown.contains(&cell) {
+            None
+        } else {
+            Some(*self.frontier.get(&cell).unwrap_or(&self.interior))
+        }
+    }
+}
+
+/// Computes the probability of each covered cell containing a mine, taking the total number of mines into account.
+/// Flags are ignored, because they might be wrong.
+pub fn probabilities(observation: &Observation) -> Probabilities {
+    let constraints = constraints(observation, &BTreeSet::new());
+    // the seed of the board must not be used, it determines where the mines are
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    let mut exact = true;
+    let mut frontier: Vec<Component> = Vec::new();
+    let mut unknown = BTreeSet::new();
+    for component in components(&constraints) {
+        let component: Vec<&Constraint> = component.iter().map(|i| &constraints[*i]).collect();
+
+        match solve_component(&component).or_else(|| sample_component(&component, &mut rng)) {
+            Some(solved) => {
+                exact &= solved.exact;
+                frontier.push(solved);
+            }
+            // no solution has been found (always the case with wrong constraints), the mines in the
+            // component are estimated like those in the interior
+            None => {
+                exact = false;
+                unknown.extend(component.iter().flat_map(|c| c.cells.keys()));
+            }
+        }
+    }
+
+    let covered = observation
+        .cells()
+        .filter(|(_, state)| !matches!(state, CellState::Uncovered(_)))
+        .count();
+    let interior = covered - frontier.iter().map(|c| c.cells.len()).sum::<usize>();
+    let mines = observation.mines_total() as usize;
+
+    // the distribution of the number of mines in each component, scaled to avoid overflows
+    let distributions: Vec<Vec<f64>> = frontier
+        .iter()
+        .map(|c| {
+            let max_mines = *c.solutions.keys().max().unwrap_or(&0);
+            let max_count = c.solutions.values().map(|(n, _)| *n).fold(0.0, f64::max);
+            let mut d = vec![0.0; max_mines + 1];
+            for (m, (n, _)) in &c.solutions {
+                d[*m] = n / max_count;
+            }
+            d
+        })
+        .collect();
+
+    // weight of k mines being placed in the interior, relative to the most likely k
+    let ln_binomial = |n: usize, k: usize| -> f64 {
+        (0..k).map(|i| ((n - i) as f64 / (i + 1) as f64).ln()).sum()
+    };
+    let interior_weights: Vec<f64> = {
+        let ln: Vec<f64> = (0..=mines.min(interior))
+            .map(|k| ln_binomial(interior, k))
+            .collect();
+        let max = ln.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        ln.iter().map(|l| (l - max).exp()).collect()
+    };
+    let interior_weight = |frontier_mines: usize| -> f64 {
+        if frontier_mines > mines {
+            0.0
+        } else {
+            *interior_weights.get(mines - frontier_mines).unwrap_or(&0.0)
+        }
+    };
+
+    // the distribution of the number of mines in all components but the one with the given index
+    let others = |skip: Option<usize>| -> Vec<f64> {
+        distributions
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != skip)
+            .fold(vec![1.0], |acc, (_, d)| convolve(&acc, d))
+    };
+
+    let all = others(None);
+    let total: f64 = all
+        .iter()
+        .enumerate()
+        .map(|(k, w)| w * interior_weight(k))
+        .sum();
+
+    if total == 0.0 {
+        // inconsistent board, e.g. more mines in the frontier than on the board
+        let p = mines as f64 / covered.max(1) as f64;
+        return Probabilities {
+            frontier: frontier
+                .iter()
+                .flat_map(|c| c.cells.iter().map(move |cell| (*cell, p)))
+                .collect(),
+            interior: p,
+            unknown,
+            exact: false,
+        };
+    }
+
+    let interior_probability = if interior == 0 {
+        0.0
+    } else {
+        all.iter()
+            .enumerate()
+            .map(|(k, w)| w * interior_weight(k) * mines.saturating_sub(k) as f64)
+            .sum::<f64>()
+            / total
+            / interior as f64
+    };
+
+    let mut result = BTreeMap::new();
+    for (i, component) in frontier.iter().enumerate() {
+        let rest = others(Some(i));
+        let max_count = component
+            .solutions
+            .values()
+            .map(|(n, _)| *n)
+            .fold(0.0, f64::max);
+
+        for (j, cell) in component.cells.iter().enumerate() {
+            let mut p = 0.0;
+            for (m, (_, per_cell)) in &component.solutions {
+                for (k, w) in rest.iter().enumerate() {
+                    p += per_cell[j] / max_count * w * interior_weight(m + k);
+                }
+            }
+            result.insert(*cell, p / total);
+        }
+    }
+
+    Probabilities {
+        frontier: result,
+        interior: interior_probability,
+        unknown,
+        exact,
+    }
+}
+
+/// Returns the convolution of a and b.
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+/// Returns the cells that are certainly safe or certainly mines, using the numbers of the uncovered cells.
+/// The simplest rules are tried first: each number on its own, then pairs of numbers whose covered cells
+/// are subsets of each other, then all solutions of each connected part of the frontier, and finally the total
+/// number of mines.
+pub fn deduce(observation: &Observation, known_mines: &BTreeSet<Cell>) -> Vec<Deduction> {
+    let covered: BTreeSet<Cell> = observation
+        .cells()
+        .filter(|(cell, state)| {
+            !matches!(state, CellState::Uncovered(_)) && !known_mines.contains(cell)
+        })
+        .map(|(cell, _)| cell)
+        .collect();
+    let count = MineCount {
+        cells: &covered,
+        mines: observation.mines_total() as i32 - known_mines.len() as i32,
+    };
+
+    deduce_from(&constraints(observation, known_mines), Some(&count))
+}
+
+/// Returns the cells whose content follows from the given constraints and the number of mines left, if known.
+fn deduce_from(constraints: &[Constraint], count: Option<&MineCount>) -> Vec<Deduction> {
+    // single-cell rules
+    let mut result: Vec<Deduction> = constraints.iter().flat_map(|c| c.deduce()).collect();
+
+    // no mines or only mines are left
+    if let Some(count) = count.filter(|c| c.mines == 0 || c.mines == c.cells.len() as i32) {
+        result.extend(count.cells.iter().map(|cell| Deduction {
+            cell: *cell,
+            mine: count.mines > 0,
+            reason: Vec::new(),
+        }));
+    }
+
+    // subset rules
+    if result.is_empty() {
+        // only constraints that share a cell can be subsets of each other
+        let mut by_cell: BTreeMap<Cell, Vec<usize>> = BTreeMap::new();
+        for (i, c) in constraints.iter().enumerate() {
+            for cell in c.cells.keys() {
+                by_cell.entry(*cell).or_default().push(i);
+            }
+        }
+
+        for (i, a) in constraints.iter().enumerate() {
+            let candidates: BTreeSet<usize> = a
+                .cells
+                .keys()
+                .flat_map(|cell| by_cell[cell].iter().copied())
+                .filter(|j| *j != i)
+                .collect();
+
+            for j in candidates {
+                if let Some(difference) = constraints[j].difference(a) {
+                    result.extend(difference.deduce());
+                }
+            }
+        }
+    }
+
+    // constraint propagation
+    let mut frontier = Vec::new();
+    if result.is_empty() {
+        for component in components(constraints) {
+            let component: Vec<&Constraint> = component.iter().map(|i| &constraints[*i]).collect();
+            let solved = solve_component(&component);
+            let cells = component
+                .iter()
+                .flat_map(|c| c.cells.keys())
+                .collect::<BTreeSet<_>>()
+                .len();
+
+            if let Some(solved) = &solved {
+                let total: f64 = solved.solutions.values().map(|(n, _)| n).sum();
+
+                for (i, cell) in solved.cells.iter().enumerate() {
+                    let mines: f64 = solved.solutions.values().map(|(_, m)| m[i]).sum();
+
+                    if mines == 0.0 || mines == total {
+                        result.push(Deduction {
+                            cell: *cell,
+                            mine: mines == total,
+                            reason: solved.sources.clone(),
+                        });
+                    }
+                }
+            }
+            frontier.push((cells, solved));
+        }
+    }
+
+    // counting the mines
+    if let Some(count) = count.filter(|_| result.is_empty()) {
+        result = deduce_from_count(constraints, &frontier, count);
+    }
+
+    // a cell might be deduced by multiple constraints
+    let mut seen = BTreeSet::new();
+    result.retain(|d| seen.insert(d.cell));
+    result
+}
+
+/// Returns the cells whose content follows from the number of mines left, given the size and the solutions (None
+/// if they are unknown) of each component of the frontier. The covered cells that aren't part of the frontier may
+/// hold any number of the remaining mines.
+fn deduce_from_count(
+    constraints: &[Constraint],
+    frontier: &[(usize, Option<Component>)],
+    count: &MineCount,
+) -> Vec<Deduction> {
+    if count.mines < 0 {
+        return Vec::new();
+    }
+    let mines = count.mines as usize;
+    let frontier_cells: BTreeSet<Cell> = constraints
+        .iter()
+        .flat_map(|c| c.cells.keys().copied())
+        .collect();
+    let interior: Vec<Cell> = count
+        .cells
+        .iter()
+        .filter(|cell| !frontier_cells.contains(cell))
+        .copied()
+        .collect();
+
+    // the numbers of mines each component may hold, followed by the interior
+    let mut possible: Vec<Vec<bool>> = frontier
+        .iter()
+        .map(|(cells, solved)| match solved {
+            Some(solved) => (0..=*cells)
+                .map(|m| solved.solutions.contains_key(&m))
+                .collect(),
+            None => vec![true; cells + 1],
+        })
+        .collect();
+    possible.push(vec![true; interior.len() + 1]);
+
+    // the numbers of mines the parts before and after each part may hold together, up to the mines left
+    let add = |a: &[bool], b: &[bool]| -> Vec<bool> {
+        let mut result = vec![false; (a.len() + b.len() - 1).min(mines + 1)];
+        for (i, _) in a.iter().enumerate().filter(|(_, x)| **x) {
+            for (j, _) in b.iter().enumerate().filter(|(_, y)| **y) {
+                if i + j <= mines {
+                    result[i + j] = true;
+                }
+            }
+        }
+        result
+    };
+    let mut before = vec![vec![true]];
+    for p in &possible {
+        before.push(add(before.last().unwrap(), p));
+    }
+    let mut after = vec![vec![true]];
+    for p in possible.iter().rev() {
+        after.push(add(after.last().unwrap(), p));
+    }
+    after.reverse();
+
+    // the numbers of mines each part may hold while the remaining mines are placed in the other parts
+    let feasible = |part: usize, m: usize| -> bool {
+        possible[part][m]
+            && m <= mines
+            && (0..=mines - m).any(|a| {
+                before[part].get(a) == Some(&true)
+                    && after[part + 1].get(mines - m - a) == Some(&true)
+            })
+    };
+
+    let mut result = Vec::new();
+    for (part, (_, solved)) in frontier.iter().enumerate() {
+        let solved = match solved {
+            Some(solved) => solved,
+            None => continue,
+        };
+        let solutions: Vec<&(f64, Vec<f64>)> = solved
+            .solutions
+            .iter()
+            .filter(|(m, _)| feasible(part, **m))
+            .map(|(_, s)| s)
+            .collect();
+        let total: f64 = solutions.iter().map(|(n, _)| n).sum();
+        if total == 0.0 {
+            continue;
+        }
+
+        for (i, cell) in solved.cells.iter().enumerate() {
+            let mines: f64 = solutions.iter().map(|(_, m)| m[i]).sum();
+
+            if mines == 0.0 || mines == total {
+                result.push(Deduction {
+                    cell: *cell,
+                    mine: mines == total,
+                    reason: solved.sources.clone(),
+                });
+            }
+        }
+    }
+
+    let part = frontier.len();
+    let interior_mines: Vec<usize> = (0..=interior.len())
+        .filter(|m| feasible(part, *m))
+        .collect();
+    if let [m] = interior_mines[..] {
+        if m == 0 || m == interior.len() {
+            result.extend(interior.iter().map(|cell| Deduction {
+                cell: *cell,
+                mine: m > 0,
+                reason: Vec::new(),
+            }));
+        }
+    }
+
+    result
+}
+
+/// Returns a deduction that helps the player, i.e. a covered cell that is safe or an unflagged mine.
+/// Mines that have been deduced before are used for further deductions, flags are ignored.
+pub fn hint(observation: &Observation) -> Option<Deduction> {
+    let mut known_mines = BTreeSet::new();
+
+    loop {
+        let deductions = deduce(observation, &known_mines);
+
+        let helpful = deductions
+            .iter()
+            .find(|d| !d.mine || observation.cell(d.cell) != CellState::Flagged);
+        if helpful.is_some() {
+            return helpful.cloned();
+        }
+
+        // all deduced mines are flagged, try again knowing them
+        let count = known_mines.len();
+        known_mines.extend(deductions.iter().map(|d| d.cell));
+        if known_mines.len() == count {
+            return None;
+        }
+    }
+}
+
+/// Plays the board without guessing, starting by uncovering the given cell.
+/// Returns whether all empty cells have been uncovered and the number of uncovered cells.
+/// The constraints are kept between the deductions, only those of the cells next to the cells that have been
+/// uncovered or found to be mines are updated.
+pub fn solve_from(board: &Board, start: Cell) -> (bool, usize) {
+    let mut board = board.clone();
+    board.cheats_remaining = 0;
+
+    // remove flags and marks, they would prevent cells from being uncovered
+    board.board.iter_mut().for_each(|value| *value &= 0x3fff);
+
+    if board.uncover_cell(start) != Uncovered::Safe {
+        return (false, 0);
+    }
+
+    // the constraints by the cell they are derived from, and the constraints each covered cell is part of
+    let mut constraints: BTreeMap<Cell, Constraint> = BTreeMap::new();
+    let mut by_cell: BTreeMap<Cell, Vec<Cell>> = BTreeMap::new();
+    let mut known_mines = BTreeSet::new();
+    let mut covered: BTreeSet<Cell> = (0..board.board.len())
+        .filter(|cell| !Board::is_uncovered(board.board[*cell]))
+        .collect();
+    // the cells that have been uncovered or found to be mines since the constraints have been updated
+    let mut dirty: Vec<Cell> = (0..board.board.len())
+        .filter(|cell| Board::is_uncovered(board.board[*cell]))
+        .collect();
+
+    loop {
+        let observation = board.observe();
+
+        for cell in dirty.drain(..) {
+            covered.remove(&cell);
+
+            // the cell is no longer part of the constraints of its neighbours
+            for source in by_cell.remove(&cell).unwrap_or_default() {
+                if let Some(constraint) = constraints.get_mut(&source) {
+                    let weight = constraint.cells.remove(&cell).unwrap_or(0);
+                    if known_mines.contains(&cell) {
+                        constraint.mines -= weight;
+                    }
+                    if constraint.cells.is_empty() {
+                        constraints.remove(&source);
+                    }
+                }
+            }
+
+            let mines = match observation.cell(cell) {
+                CellState::Uncovered(mines) => mines,
+                _ => continue,
+            };
+            let mut constraint = Constraint {
+                sources: vec![cell],
+                cells: BTreeMap::new(),
+                mines: mines as i32,
+            };
+            for n in observation.neighbors(cell) {
+                if known_mines.contains(&n) {
+                    constraint.mines -= 1;
+                } else if covered.contains(&n) {
+                    *constraint.cells.entry(n).or_insert(0) += 1;
+                }
+            }
+            if !constraint.cells.is_empty() {
+                for n in constraint.cells.keys() {
+                    by_cell.entry(*n).or_default().push(cell);
+                }
+                constraints.insert(cell, constraint);
+            }
+        }
+
+        let count = MineCount {
+            cells: &covered,
+            mines: board.mines_total as i32 - known_mines.len() as i32,
+        };
+        let current: Vec<Constraint> = constraints.values().cloned().collect();
+        let deductions = deduce_from(&current, Some(&count));
+        if deductions.is_empty() {
+            break;
+        }
+
+        for d in deductions {
+            if d.mine {
+                if known_mines.insert(d.cell) {
+                    dirty.push(d.cell);
+                }
+            } else {
+                let moves = board.moves.len();
+                board.uncover_cell(d.cell);
+                if let Some(m) = board.moves.get(moves) {
+                    dirty.extend(m.changes.iter().map(|(cell, _, _)| *cell));
+                }
+            }
+        }
+    }
+
+    let uncovered = board
+        .board
+        .iter()
+        .filter(|value| Board::is_uncovered(**value))
+        .count();
+    let empty = board
+        .board
+        .iter()
+        .filter(|value| Board::is_empty(**value))
+        .count();
+
+    (uncovered == empty, uncovered)
+}
@@ -0,0 +1,342 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod solver_tests {
+
+    use super::super::{
+        deduce, deduce_from, hint, probabilities, sample_component, solve_component, solve_from,
+        Constraint, MineCount,
+    };
+    use crate::board::{
+        Board, Boundary, Generator, Neighbourhood, Rules, SafeStart, Topology, Uncovered,
+    };
+    use crate::test_boards::one_mine;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_single_cell_rule() {
+        // 1D board: · 1 * (mine at x₁ = 2)
+        let mut board = Board::new(
+            &[3],
+            0,
+            0,
+            Rules {
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        board.board[0] = 0xc000;
+        board.board[1] = 0xc001;
+        board.board[2] = 0x2001;
+        board.mines_total = 1;
+
+        let deductions = deduce(&board.observe(), &BTreeSet::new());
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].cell, 2);
+        assert!(deductions[0].mine);
+        assert_eq!(deductions[0].reason, vec![1]);
+    }
+
+    #[test]
+    fn test_subset_rule() {
+        // 2D board, first row uncovered: 1 1 1 / second row covered, mine at x₁ = 0
+        let mut board = Board::new(
+            &[3, 2],
+            0,
+            0,
+            Rules {
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        board.board[0] = 0xc001;
+        board.board[1] = 0xc000;
+        board.board[2] = 0xc000;
+        board.board[3] = 0x2001;
+        board.board[4] = 0x0001;
+        board.board[5] = 0x0000;
+        board.mines_total = 1;
+
+        let deductions = deduce(&board.observe(), &BTreeSet::new());
+        let mines: Vec<_> = deductions.iter().filter(|d| d.mine).collect();
+        assert_eq!(mines.len(), 1);
+        assert_eq!(mines[0].cell, 3);
+    }
+
+    #[test]
+    fn test_no_guess() {
+        for seed in 0..5 {
+            let mut board = Board::new(
+                &[6, 6, 3],
+                18,
+                0,
+                Rules {
+                    generator: Generator::NoGuess(200),
+                    ..Rules::default()
+                },
+                Some(seed),
+            );
+            assert_eq!(board.uncover_cell(57), Uncovered::Safe);
+
+            assert_eq!(board.solvable, Some(true));
+            assert!(solve_from(&board, 57).0);
+            assert_eq!(
+                board.board.iter().filter(|v| !Board::is_empty(**v)).count(),
+                18
+            );
+        }
+    }
+
+    fn constraint(cells: &[usize], mines: i32) -> Constraint {
+        Constraint {
+            sources: vec![0],
+            cells: cells.iter().map(|x| (10 + *x, 1)).collect(),
+            mines,
+        }
+    }
+
+    #[test]
+    fn test_solve_component() {
+        // a + b = 1 and b + c = 1 have the solutions (0, 1, 0) and (1, 0, 1)
+        let constraints = [constraint(&[0, 1], 1), constraint(&[1, 2], 1)];
+        let component = solve_component(&constraints.iter().collect::<Vec<_>>()).unwrap();
+
+        assert_eq!(component.solutions.len(), 2);
+        assert_eq!(component.solutions[&1], (1.0, vec![0.0, 1.0, 0.0]));
+        assert_eq!(component.solutions[&2], (1.0, vec![1.0, 0.0, 1.0]));
+
+        // contradicting constraints have no solutions
+        let constraints = [constraint(&[0, 1], 2), constraint(&[1, 2], 0)];
+        let component = solve_component(&constraints.iter().collect::<Vec<_>>()).unwrap();
+        assert!(component.solutions.is_empty());
+    }
+
+    #[test]
+    fn test_constraint_propagation() {
+        // a + b + c = 1, c + d + e = 1 and a + b + d + e = 2 imply c = 0, no two constraints are subsets
+        let constraints = [
+            constraint(&[0, 1, 2], 1),
+            constraint(&[2, 3, 4], 1),
+            constraint(&[0, 1, 3, 4], 2),
+        ];
+
+        let deductions = deduce_from(&constraints, None);
+        assert_eq!(deductions.len(), 1);
+        assert_eq!(deductions[0].cell, 12);
+        assert!(!deductions[0].mine);
+    }
+
+    #[test]
+    fn test_mine_count() {
+        // a + b = 1 and b + c = 1 with 2 mines left: a and c are mines, b is safe
+        let constraints = [constraint(&[0, 1], 1), constraint(&[1, 2], 1)];
+        let cells = (10..13).collect();
+        let deductions = deduce_from(
+            &constraints,
+            Some(&MineCount {
+                cells: &cells,
+                mines: 2,
+            }),
+        );
+        let mines: Vec<_> = deductions.iter().map(|d| (d.cell, d.mine)).collect();
+        assert_eq!(mines, [(10, true), (11, false), (12, true)]);
+
+        // a + b = 1 with 1 mine left: the cells outside of the frontier are safe
+        let constraints = [constraint(&[0, 1], 1)];
+        let cells = (10..14).collect();
+        let deductions = deduce_from(
+            &constraints,
+            Some(&MineCount {
+                cells: &cells,
+                mines: 1,
+            }),
+        );
+        let safe: Vec<_> = deductions.iter().map(|d| (d.cell, d.mine)).collect();
+        assert_eq!(safe, [(12, false), (13, false)]);
+
+        // 0 1 * 1 0: the cells behind the mine can only be uncovered by counting the mines
+        assert_eq!(solve_from(&one_mine(0), 0), (true, 4));
+    }
+
+    #[test]
+    fn test_solve_from_incrementally() {
+        // the constraints kept by solve_from lead to the same result as deducing from the whole board each time
+        for (seed, boundary) in [Boundary::Bounded, Boundary::Periodic, Boundary::Mirrored]
+            .iter()
+            .enumerate()
+        {
+            let rules = Rules {
+                neighbourhood: Neighbourhood::Moore,
+                topology: Topology::uniform(*boundary),
+                safe_start: SafeStart::Neighbourhood,
+                ..Rules::default()
+            };
+            let mut board = Board::new(&[8, 8, 3], 12, 0, rules, Some(seed as u64));
+            let start = board.index(&[4, 4, 1]).unwrap();
+            board.uncover_cell(start);
+
+            let mut expected = board.clone();
+            let mut known_mines = BTreeSet::new();
+            loop {
+                let deductions = deduce(&expected.observe(), &known_mines);
+                if deductions.is_empty() {
+                    break;
+                }
+                for d in deductions {
+                    if d.mine {
+                        known_mines.insert(d.cell);
+                    } else {
+                        expected.uncover_cell(d.cell);
+                    }
+                }
+            }
+            let uncovered = expected
+                .board
+                .iter()
+                .filter(|v| Board::is_uncovered(**v))
+                .count();
+
+            assert_eq!(solve_from(&board, start).1, uncovered);
+        }
+    }
+
+    #[test]
+    fn test_hint() {
+        // 1D board: 1 * 1 # # with the mine already flagged, the hint is the cell next to the second 1
+        let mut board = Board::new(
+            &[5],
+            0,
+            0,
+            Rules {
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        board.board[0] = 0xc001;
+        board.board[1] = 0x2001;
+        board.board[2] = 0xc001;
+        board.mines_total = 1;
+
+        let h = hint(&board.observe()).unwrap();
+        assert_eq!(h.cell, 1);
+        assert!(h.mine);
+        assert_eq!(h.reason, vec![0]);
+
+        board.flag_cell(1);
+        let h = hint(&board.observe()).unwrap();
+        assert_eq!(h.cell, 3);
+        assert!(!h.mine);
+
+        board.uncover_cell(3);
+        assert_eq!(hint(&board.observe()), None);
+    }
+
+    #[test]
+    fn test_probabilities() {
+        // 1D board: # 1 # # # #, the mine next to the 1 is on either side
+        let mut board = Board::new(
+            &[6],
+            0,
+            0,
+            Rules {
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        board.board[0] = 0x2001;
+        board.board[1] = 0xc001;
+        board.mines_total = 1;
+
+        let p = probabilities(&board.observe());
+        assert!(p.exact);
+        assert!((p.get(0).unwrap() - 0.5).abs() < 1e-9);
+        assert!((p.get(2).unwrap() - 0.5).abs() < 1e-9);
+        assert_eq!(p.get(5).unwrap(), 0.0);
+
+        // with two mines the second one is in one of the three interior cells
+        board.mines_total = 2;
+        let p = probabilities(&board.observe());
+        assert!((p.get(0).unwrap() - 0.5).abs() < 1e-9);
+        assert!((p.interior - 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_probabilities_global_count() {
+        // 1D board: # 1 # 1 #, i.e. a + b = 1 and b + c = 1
+        let mut board = Board::new(
+            &[5],
+            0,
+            0,
+            Rules {
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        board.board[1] = 0xc001;
+        board.board[3] = 0xc001;
+
+        // a single mine has to be b
+        board.mines_total = 1;
+        let p = probabilities(&board.observe());
+        assert_eq!(p.get(0).unwrap(), 0.0);
+        assert_eq!(p.get(2).unwrap(), 1.0);
+
+        // two mines have to be a and c
+        board.mines_total = 2;
+        let p = probabilities(&board.observe());
+        assert_eq!(p.get(0).unwrap(), 1.0);
+        assert_eq!(p.get(2).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_sample_component() {
+        let constraints = [constraint(&[0, 1], 1), constraint(&[1, 2], 1)];
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let component =
+            sample_component(&constraints.iter().collect::<Vec<_>>(), &mut rng).unwrap();
+
+        assert!(!component.exact);
+        let samples: f64 = component.solutions.values().map(|(n, _)| n).sum();
+        assert_eq!(samples, 200.0);
+        assert_eq!(component.solutions.len(), 2);
+    }
+
+    #[test]
+    fn test_probabilities_large_component() {
+        // 600×3 board with the middle row uncovered, the covered rows form a single component that has too many
+        // solutions to be enumerated, because each mine could also be on the other side of the middle row
+        let mut board = Board::new(
+            &[600, 3],
+            0,
+            0,
+            Rules {
+                neighbourhood: Neighbourhood::Moore,
+                safe_start: SafeStart::Off,
+                ..Rules::default()
+            },
+            None,
+        );
+        for x1 in (0..600).step_by(3) {
+            board.board[x1] = 0x2000;
+        }
+        for cell in 600..1200 {
+            let mines = board
+                .neighbors(cell)
+                .filter(|n| board.board[*n] & 0x2000 != 0)
+                .count();
+            board.board[cell] = 0xc000 | mines as u16;
+        }
+        board.mines_total = 200;
+
+        let p = probabilities(&board.observe());
+        assert!(!p.exact);
+        assert!(p.unknown.is_empty());
+        assert_eq!(p.frontier.len(), 1200);
+    }
+}
@@ -0,0 +1,14 @@
+//! Boards shared by the tests of the library.
+
+use crate::board::{Board, Rules, SafeStart};
+
+/// Returns the 1D board 0 1 * 1 0 with the given number of cheats, its mine has already been placed.
+pub fn one_mine(cheats: u32) -> Board {
+    let rules = Rules {
+        safe_start: SafeStart::Off,
+        ..Rules::default()
+    };
+    let mut board = Board::new(&[5], 0, cheats, rules, Some(0));
+    board.place_mines_at(&[2]);
+    board
+}